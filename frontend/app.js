@@ -9,7 +9,8 @@ const state = {
     ollamaEnabled: false,
     ollamaModelsCache: [],
     slashVisible: false,
-    slashIndex: -1
+    slashIndex: -1,
+    includeImages: false // yalnızca sıradaki soru için
 };
 
 // DOM Elements
@@ -260,9 +261,19 @@ function setupEventListeners() {
     });
     
     sendButton.addEventListener('click', handleChatSubmit);
+    document.getElementById('include-images').addEventListener('click', () => setIncludeImages(!state.includeImages));
     
     // Chat controls
     document.getElementById('clear-chat').addEventListener('click', clearChat);
+    document.getElementById('strategy-add-default').addEventListener('click', addDefaultStrategyChain);
+    document.getElementById('proxy-test').addEventListener('click', testProxy);
+    document.getElementById('cache-purge-domain-btn').addEventListener('click', () => purgePageCache(true));
+    document.getElementById('cache-purge-all').addEventListener('click', () => purgePageCache(false));
+    document.getElementById('answer-language-mode').addEventListener('change', (e) => {
+        const fixed = document.getElementById('answer-language-fixed');
+        if (fixed) fixed.disabled = e.target.value !== 'fixed';
+    });
+    document.getElementById('export-tables').addEventListener('click', (e) => exportPageTables(e.shiftKey ? 'json' : 'csv'));
     document.getElementById('minimize-chat').addEventListener('click', toggleChat);
     chatToggle.addEventListener('click', toggleChat);
     
@@ -286,6 +297,16 @@ function setupEventListeners() {
         handleSlashInput();
     });
 
+    // Cevaptaki bağlantılar uygulama penceresini değil aktif sekmeyi yönlendirsin
+    chatMessages.addEventListener('click', (e) => {
+        const a = e.target.closest('a[href]');
+        if (!a) return;
+        const href = a.getAttribute('href');
+        if (!/^https?:\/\//i.test(href)) return;
+        e.preventDefault();
+        loadUrl(href);
+    });
+
     // Add window resize listener
     window.addEventListener('resize', updateBrowserViewPositionAndSize);
 
@@ -335,6 +356,7 @@ function setupEventListeners() {
     const tauriEvent = window.__TAURI__ && window.__TAURI__.event;
     if (tauriEvent && typeof tauriEvent.listen === 'function') {
         let currentBotMessageDiv = null;
+        let lastBotMessageDiv = null;
         let fullResponse = '';
 
         // İçerik kaynağı bilgisi: hem konsola logla hem de sohbete kısa sistem mesajı ekle
@@ -344,13 +366,34 @@ function setupEventListeners() {
             console.group('[CONTENT SOURCE]');
             console.log('mode:', p.mode);
             console.log('url:', p.url);
-            console.log('source:', p.source); // youtube_light | firecrawl_scrape | firecrawl_crawl | http_fallback | cache_unknown
+            console.log('source:', p.source); // site_crawl | site_firecrawl | live_dom | youtube_transcript | youtube_light | firecrawl_scrape | firecrawl_crawl | aggressive_html | http_fallback | pdf | feed
+            if (p.encoding) console.log('encoding:', `${p.encoding.name} (${p.encoding.via})`);
             console.log('from_cache:', p.from_cache);
             console.log('length:', p.length);
+            if (p.cleanup) console.log('cleanup:', `${p.cleanup.before} → ${p.cleanup.after} B (${p.cleanup.removedBlocks} blok, ${p.cleanup.removedLines} satır${p.cleanup.reverted ? ', geri alındı' : ''})`);
+            if (p.attempts && p.attempts.length) console.table(p.attempts.map(a => ({ strategy: a.strategy, outcome: a.outcome, ms: a.durationMs, score: a.score ?? '', error: a.error || '' })));
+            if (p.links) console.log('links:', p.links);
+            console.log('cookies:', !!p.cookies);
+            if (p.metadata) console.log('metadata:', p.metadata);
+            if (p.language) console.log('language:', `${p.language.name} (${p.language.code}, ${p.language.confidence})`, '→ answer:', p.answer_language || 'question');
+            if (p.images && p.images.length) console.log('images:', p.images.map(i => `${i.url} (${i.mime}, ${i.size} B)`));
             console.log('preview:', p.preview);
             console.groupEnd();
         });
 
+        // Model görselleri kabul etmedi, soru görselsiz gönderildi
+        tauriEvent.listen('vision-fallback', (event) => {
+            const model = event && event.payload ? event.payload.model : '';
+            updateChatStatus(`${model} görsel desteklemiyor, görselsiz soruldu`);
+        });
+
+        // Site taraması ilerlemesi (/site)
+        tauriEvent.listen('crawl-progress', (event) => {
+            const p = event && event.payload ? event.payload : null;
+            if (!p) return;
+            updateChatStatus(`Site taranıyor: ${p.done}/${p.total}`);
+        });
+
         tauriEvent.listen('ollama-stream', (event) => {
             const payload = event && event.payload ? event.payload : null;
 
@@ -376,6 +419,7 @@ function setupEventListeners() {
                 if (!fullResponse) {
                     currentBotMessageDiv.textContent = '(cevap üretilemedi)';
                 }
+                lastBotMessageDiv = currentBotMessageDiv;
                 currentBotMessageDiv = null;
                 fullResponse = '';
                 sendButton.disabled = false;
@@ -401,6 +445,7 @@ function setupEventListeners() {
             }
             if (payload.done) {
                 if (!fullResponse) { currentBotMessageDiv.textContent = '(cevap üretilemedi)'; }
+                lastBotMessageDiv = currentBotMessageDiv;
                 currentBotMessageDiv = null;
                 fullResponse = '';
                 sendButton.disabled = false;
@@ -408,6 +453,18 @@ function setupEventListeners() {
             }
         });
 
+        // Cevaptaki [L3] atıfları backend'de gerçek adreslere çevrildi; son cevabı yeniden çiz
+        tauriEvent.listen('answer-links', (event) => {
+            const p = event && event.payload ? event.payload : null;
+            if (!p || !p.text || !lastBotMessageDiv) return;
+            if (window.marked) {
+                lastBotMessageDiv.innerHTML = marked.parse(p.text);
+            } else {
+                lastBotMessageDiv.textContent = p.text;
+            }
+            console.log('[LINKS] atıflar:', (p.links || []).map(l => `[L${l.n}] ${l.url}`));
+        });
+
         // OpenRouter fallback model değişimleri
         tauriEvent.listen('openrouter-model-fallback', (event) => {
             const p = event && event.payload ? event.payload : null;
@@ -1054,6 +1111,8 @@ const SLASH_COMMANDS = [
     { cmd: '/kaynakekle', hint: 'Kaynakları belirt' },
     { cmd: '/kisalt', hint: 'Daha kısa yaz' },
     { cmd: '/uzat', hint: 'Daha detaylı yaz' },
+    { cmd: '/site', hint: 'Tüm siteyi tarayıp cevapla' },
+    { cmd: '/yeni', hint: 'Sitenin RSS/Atom akışındaki son kayıtlar' },
 ];
 
 function handleSlashInput() {
@@ -1127,10 +1186,11 @@ function insertSlashCommand(cmd) {
 
 function parseSlashDirective(text) {
     const m = text.match(/^\s*\/(\w+)\b(.*)$/);
-    if (!m) return { clean: text, directive: null };
+    if (!m) return { clean: text, directive: null, scope: null };
     const keyword = m[1].toLowerCase();
     const rest = (m[2] || '').trim();
     let directive = null;
+    let scope = null;
     switch (keyword) {
         case 'ozetle':
             directive = 'Biçim: Kısa ve öz bir özet ver.';
@@ -1142,7 +1202,7 @@ function parseSlashDirective(text) {
             directive = 'Biçim: Maddeler halinde açıkla.';
             break;
         case 'kaynakekle':
-            directive = 'Biçim: Varsa kaynak/bağlantıları ekle.';
+            directive = 'Biçim: Kaynak/bağlantıları içerikteki Bağlantılar listesinden [L3] gibi numaralarıyla belirt.';
             break;
         case 'kisalt':
             directive = 'Biçim: Daha kısa yaz.';
@@ -1150,11 +1210,21 @@ function parseSlashDirective(text) {
         case 'uzat':
             directive = 'Biçim: Daha detaylı yaz.';
             break;
+        case 'site':
+            // Tek sayfa yerine site derlemi üzerinden cevap (backend scope=site)
+            directive = 'Kapsam: Sitenin birden çok sayfasından derlenen içerik. Her bilgi için [Sayfa N] etiketiyle kaynağı belirt.';
+            scope = 'site';
+            break;
+        case 'yeni':
+            // Sayfanın RSS/Atom/JSON Feed akışındaki son kayıtlar üzerinden cevap (backend scope=feed)
+            directive = 'Kapsam: Sitenin akışındaki son kayıtlar. Kayıtların tarihlerini ve bağlantılarını belirt.';
+            scope = 'feed';
+            break;
         default:
-            return { clean: text, directive: null };
+            return { clean: text, directive: null, scope: null };
     }
     const clean = rest || inferDefaultQuestionFor(keyword);
-    return { clean, directive };
+    return { clean, directive, scope };
 }
 
 function inferDefaultQuestionFor(keyword) {
@@ -1165,6 +1235,8 @@ function inferDefaultQuestionFor(keyword) {
         case 'kaynakekle': return 'Bu içerikle ilgili kaynak/bağlantıları ekler misin?';
         case 'kisalt': return 'Bu yanıtı daha kısa yazar mısın?';
         case 'uzat': return 'Bu yanıtı daha detaylı yazar mısın?';
+        case 'site': return 'Bu site ne hakkında? Önemli sayfaları özetler misin?';
+        case 'yeni': return 'Bu sitede neler yeni? Son yazıları özetler misin?';
         default: return '';
     }
 }
@@ -1282,6 +1354,17 @@ async function openSettings() {
         if (tauriInvoke) {
             const current = await tauriInvoke('get_ollama_base_url');
             if (ollamaUrlInput) ollamaUrlInput.value = current || 'http://localhost:11434';
+            fillFirecrawlSettings(await tauriInvoke('get_firecrawl_config'));
+            fillCrawlSettings(await tauriInvoke('get_crawl_options'));
+            fillPoliteSettings(await tauriInvoke('get_polite_config'));
+            fillProxySettings(await tauriInvoke('get_proxy_config'));
+            fillHttpSettings(await tauriInvoke('get_http_config'));
+            fillCacheSettings(await tauriInvoke('get_page_cache_config'));
+            await refreshCacheStats();
+            fillCookieSettings(await tauriInvoke('get_cookie_sharing'));
+            fillAnswerLanguage(await tauriInvoke('get_answer_language'));
+            fillStrategyChains(await tauriInvoke('get_strategy_chains'));
+            fillRaceSettings(await tauriInvoke('get_race_options'));
         }
     } catch (e) {
         console.error('Ayarlar okunamadı:', e);
@@ -1340,6 +1423,277 @@ function updateModelSelector() {
     }
 }
 
+function fillFirecrawlSettings(cfg) {
+    if (!cfg) return;
+    const opts = cfg.options || {};
+    const set = (id, value) => { const el = document.getElementById(id); if (el) el.value = value ?? ''; };
+    set('fc-base-url', cfg.baseUrl);
+    set('fc-api-version', cfg.apiVersion || 'v0');
+    set('fc-api-key', cfg.apiKey);
+    set('fc-wait-for', opts.waitFor);
+    set('fc-timeout', opts.timeout);
+    set('fc-include-tags', (opts.includeTags || []).join(', '));
+    set('fc-exclude-tags', (opts.excludeTags || []).join(', '));
+    const onlyMain = document.getElementById('fc-only-main');
+    if (onlyMain) onlyMain.checked = opts.onlyMainContent !== false;
+}
+
+function readFirecrawlSettings() {
+    const val = (id) => (document.getElementById(id)?.value || '').trim();
+    const num = (id) => { const n = parseInt(val(id), 10); return Number.isFinite(n) && n > 0 ? n : null; };
+    const list = (id) => val(id).split(',').map(s => s.trim()).filter(Boolean);
+    return {
+        baseUrl: val('fc-base-url') || 'https://api.firecrawl.dev',
+        apiVersion: val('fc-api-version') || 'v0',
+        apiKey: val('fc-api-key') || null,
+        options: {
+            onlyMainContent: !!document.getElementById('fc-only-main')?.checked,
+            waitFor: num('fc-wait-for'),
+            formats: ['markdown'],
+            includeTags: list('fc-include-tags'),
+            excludeTags: list('fc-exclude-tags'),
+            timeout: num('fc-timeout')
+        }
+    };
+}
+
+function fillCrawlSettings(opts) {
+    if (!opts) return;
+    const set = (id, value) => { const el = document.getElementById(id); if (el) el.value = value ?? ''; };
+    set('crawl-backend', opts.backend || 'local');
+    set('crawl-max-depth', opts.maxDepth);
+    set('crawl-max-pages', opts.maxPages);
+    const sitemap = document.getElementById('crawl-use-sitemap');
+    if (sitemap) sitemap.checked = opts.useSitemap !== false;
+    const sameOrigin = document.getElementById('crawl-same-origin');
+    if (sameOrigin) sameOrigin.checked = opts.sameOrigin !== false;
+}
+
+function readCrawlSettings() {
+    const num = (id, fallback) => { const n = parseInt(document.getElementById(id)?.value, 10); return Number.isFinite(n) && n >= 0 ? n : fallback; };
+    return {
+        backend: document.getElementById('crawl-backend')?.value || 'local',
+        maxDepth: num('crawl-max-depth', 2),
+        maxPages: num('crawl-max-pages', 15),
+        useSitemap: !!document.getElementById('crawl-use-sitemap')?.checked,
+        sameOrigin: !!document.getElementById('crawl-same-origin')?.checked
+    };
+}
+
+function fillPoliteSettings(cfg) {
+    if (!cfg) return;
+    const set = (id, value) => { const el = document.getElementById(id); if (el) el.value = value ?? ''; };
+    set('polite-user-agent', cfg.userAgent);
+    set('polite-rps', cfg.requestsPerSecond);
+    set('polite-burst', cfg.burst);
+    const robots = document.getElementById('polite-respect-robots');
+    if (robots) robots.checked = cfg.respectRobots !== false;
+}
+
+function readPoliteSettings() {
+    const val = (id) => (document.getElementById(id)?.value || '').trim();
+    const rps = parseFloat(val('polite-rps'));
+    const burst = parseInt(val('polite-burst'), 10);
+    return {
+        userAgent: val('polite-user-agent'),
+        respectRobots: !!document.getElementById('polite-respect-robots')?.checked,
+        requestsPerSecond: Number.isFinite(rps) && rps > 0 ? rps : 1,
+        burst: Number.isFinite(burst) && burst > 0 ? burst : 3
+    };
+}
+
+function fillProxySettings(cfg) {
+    if (!cfg) return;
+    const set = (id, value) => { const el = document.getElementById(id); if (el) el.value = value ?? ''; };
+    set('proxy-kind', cfg.kind || 'http');
+    set('proxy-host', cfg.host);
+    set('proxy-port', cfg.port);
+    set('proxy-username', cfg.username);
+    set('proxy-password', cfg.password);
+    set('proxy-no-proxy', cfg.noProxy);
+    const enabled = document.getElementById('proxy-enabled');
+    if (enabled) enabled.checked = !!cfg.enabled;
+    const result = document.getElementById('proxy-test-result');
+    if (result) result.textContent = '';
+}
+
+function readProxySettings() {
+    const val = (id) => (document.getElementById(id)?.value || '').trim();
+    const port = parseInt(val('proxy-port'), 10);
+    return {
+        enabled: !!document.getElementById('proxy-enabled')?.checked,
+        kind: val('proxy-kind') || 'http',
+        host: val('proxy-host'),
+        port: Number.isFinite(port) ? port : 8080,
+        username: val('proxy-username') || null,
+        password: document.getElementById('proxy-password')?.value || null,
+        noProxy: val('proxy-no-proxy')
+    };
+}
+
+const HTTP_FIELDS = [
+    ['http-connect', 'connectTimeoutSecs', 10],
+    ['http-scraper', 'scraperTimeoutSecs', 20],
+    ['http-metadata', 'metadataTimeoutSecs', 10],
+    ['http-llm-first-byte', 'llmFirstByteSecs', 180],
+    ['http-read', 'readTimeoutSecs', 60]
+];
+
+function fillHttpSettings(cfg) {
+    if (!cfg) return;
+    for (const [id, key] of HTTP_FIELDS) {
+        const el = document.getElementById(id);
+        if (el) el.value = cfg[key] ?? '';
+    }
+}
+
+function readHttpSettings() {
+    const cfg = {};
+    for (const [id, key, fallback] of HTTP_FIELDS) {
+        const n = parseInt(document.getElementById(id)?.value, 10);
+        cfg[key] = Number.isFinite(n) ? n : fallback;
+    }
+    return cfg;
+}
+
+const MB = 1024 * 1024;
+
+function fillCacheSettings(cfg) {
+    if (!cfg) return;
+    const entries = document.getElementById('cache-max-entries');
+    const mb = document.getElementById('cache-max-mb');
+    if (entries) entries.value = cfg.maxEntries ?? '';
+    if (mb) mb.value = cfg.maxBytes ? Math.round(cfg.maxBytes / MB) : '';
+}
+
+function readCacheSettings() {
+    const entries = parseInt(document.getElementById('cache-max-entries')?.value, 10);
+    const mb = parseInt(document.getElementById('cache-max-mb')?.value, 10);
+    return {
+        maxEntries: Number.isFinite(entries) ? entries : 64,
+        maxBytes: (Number.isFinite(mb) ? mb : 32) * MB
+    };
+}
+
+async function refreshCacheStats() {
+    const el = document.getElementById('cache-stats');
+    if (!tauriInvoke || !el) return;
+    try {
+        const s = await tauriInvoke('get_page_cache_stats');
+        const mb = (n) => (n / MB).toFixed(1);
+        el.textContent = `Bellek: ${s.entries}/${s.maxEntries} kayıt, ${mb(s.bytes)}/${mb(s.maxBytes)} MB · `
+            + `Kalıcı: ${s.storedEntries} kayıt, ${mb(s.storedBytes)} MB · `
+            + `İsabet ${s.hits}, kalıcıdan ${s.storedHits}, ıska ${s.misses}, atılan ${s.evictions}`;
+    } catch (e) {
+        el.textContent = `Önbellek bilgisi alınamadı: ${e}`;
+    }
+}
+
+async function purgePageCache(byDomain) {
+    if (!tauriInvoke) return;
+    try {
+        let removed;
+        if (byDomain) {
+            const domain = (document.getElementById('cache-purge-domain')?.value || '').trim();
+            if (!domain) return;
+            removed = await tauriInvoke('purge_page_cache_domain', { domain });
+        } else {
+            removed = await tauriInvoke('purge_page_cache');
+        }
+        updateChatStatus(`Önbellekten ${removed} kayıt silindi`);
+    } catch (e) {
+        updateChatStatus(`Önbellek temizlenemedi: ${e}`, 'error');
+    }
+    await refreshCacheStats();
+}
+
+function fillAnswerLanguage(cfg) {
+    if (!cfg) return;
+    const mode = document.getElementById('answer-language-mode');
+    const fixed = document.getElementById('answer-language-fixed');
+    if (mode) mode.value = cfg.mode || 'auto';
+    if (fixed) {
+        fixed.value = cfg.fixed || 'tr';
+        fixed.disabled = (cfg.mode || 'auto') !== 'fixed';
+    }
+}
+
+function readAnswerLanguage() {
+    return {
+        mode: document.getElementById('answer-language-mode')?.value || 'auto',
+        fixed: document.getElementById('answer-language-fixed')?.value || 'tr'
+    };
+}
+
+function fillCookieSettings(cfg) {
+    const el = document.getElementById('cookie-domains');
+    if (el) el.value = (cfg && cfg.domains ? cfg.domains : []).join('\n');
+}
+
+function readCookieSettings() {
+    const raw = document.getElementById('cookie-domains')?.value || '';
+    return { domains: raw.split(/[\n,]/).map(d => d.trim()).filter(Boolean) };
+}
+
+// Formdaki (henüz kaydedilmemiş) vekil ayarını test eder
+async function testProxy() {
+    const result = document.getElementById('proxy-test-result');
+    if (!tauriInvoke || !result) return;
+    const config = { ...readProxySettings(), enabled: true };
+    result.textContent = 'Test ediliyor...';
+    try {
+        const r = await tauriInvoke('test_proxy', { config });
+        result.textContent = r.ok
+            ? `Bağlantı başarılı (${r.latencyMs} ms${r.exitIp ? `, çıkış IP: ${r.exitIp}` : ''})`
+            : `Bağlantı başarısız: ${r.error || 'bilinmeyen hata'}`;
+    } catch (e) {
+        result.textContent = `Bağlantı başarısız: ${e}`;
+    }
+}
+
+function fillStrategyChains(chains) {
+    const el = document.getElementById('strategy-chains');
+    if (el) el.value = chains && chains.length ? JSON.stringify(chains, null, 2) : '';
+}
+
+function readStrategyChains() {
+    const raw = (document.getElementById('strategy-chains')?.value || '').trim();
+    if (!raw) return [];
+    try {
+        const parsed = JSON.parse(raw);
+        if (!Array.isArray(parsed)) throw new Error('dizi olmalı');
+        return parsed;
+    } catch (e) {
+        throw new Error(`İçerik zinciri JSON'u geçersiz: ${e.message}`);
+    }
+}
+
+function fillRaceSettings(opts) {
+    if (!opts) return;
+    const enabled = document.getElementById('race-enabled');
+    if (enabled) enabled.checked = !!opts.enabled;
+    const deadline = document.getElementById('race-deadline');
+    if (deadline) deadline.value = opts.deadlineSecs ?? '';
+}
+
+function readRaceSettings() {
+    const n = parseInt(document.getElementById('race-deadline')?.value, 10);
+    return {
+        enabled: !!document.getElementById('race-enabled')?.checked,
+        deadlineSecs: Number.isFinite(n) && n > 0 ? n : 20
+    };
+}
+
+// Varsayılan adımlarla yeni bir kalıp şablonu ekler
+async function addDefaultStrategyChain() {
+    if (!tauriInvoke) return;
+    let chains = [];
+    try { chains = readStrategyChains(); } catch (e) { updateChatStatus(e.message, 'error'); return; }
+    const steps = await tauriInvoke('get_default_strategy_steps');
+    chains.push({ pattern: 'example.com', steps });
+    fillStrategyChains(chains);
+}
+
 function closeSettings() {
     settingsModal.classList.add('hidden');
 }
@@ -1356,6 +1710,20 @@ async function saveSettings() {
                 await tauriInvoke('set_ollama_base_url', { value });
             }
         }
+
+        // Firecrawl adresi / sürümü / seçenekleri
+        if (tauriInvoke) {
+            await tauriInvoke('set_firecrawl_config', { config: readFirecrawlSettings() });
+            await tauriInvoke('set_crawl_options', { options: readCrawlSettings() });
+            await tauriInvoke('set_polite_config', { config: readPoliteSettings() });
+            await tauriInvoke('set_proxy_config', { config: readProxySettings() });
+            await tauriInvoke('set_http_config', { config: readHttpSettings() });
+            await tauriInvoke('set_page_cache_config', { config: readCacheSettings() });
+            await tauriInvoke('set_cookie_sharing', { config: readCookieSettings() });
+            await tauriInvoke('set_answer_language', { config: readAnswerLanguage() });
+            await tauriInvoke('set_strategy_chains', { chains: readStrategyChains() });
+            await tauriInvoke('set_race_options', { options: readRaceSettings() });
+        }
         
         updateChatStatus('Ayarlar kaydedildi');
         closeSettings();
@@ -1365,7 +1733,7 @@ async function saveSettings() {
         
     } catch (e) {
         console.error('Ayar kaydedilemedi:', e);
-        updateChatStatus('Ayar kaydedilemedi', 'error');
+        updateChatStatus(`Ayar kaydedilemedi: ${e && e.message ? e.message : e}`, 'error');
     }
 }
 
@@ -1445,7 +1813,9 @@ async function handleChatSubmit() {
     }
     
     // Slash komutunu işle ve modele per-turn talimat ekle
-    const { clean, directive } = parseSlashDirective(message);
+    const { clean, directive, scope } = parseSlashDirective(message);
+    const includeImages = state.includeImages;
+    setIncludeImages(false);
 
     // Add user message to chat (kullanıcı girdisini olduğu gibi gösteriyoruz)
     addChatMessage('user', message);
@@ -1468,7 +1838,9 @@ async function handleChatSubmit() {
             await tauriInvoke('ask_question', {
                 url: state.currentUrl,
                 question: buildQuestionWithDirective(clean, directive),
-                model: state.currentModel.replace('ollama:', '')
+                model: state.currentModel.replace('ollama:', ''),
+                scope,
+                includeImages
             });
         } else {
             // OpenRouter kullan (varsayılan)
@@ -1476,7 +1848,9 @@ async function handleChatSubmit() {
             await tauriInvoke('ask_question_openrouter', {
                 url: state.currentUrl,
                 question: buildQuestionWithDirective(clean, directive),
-                model: model
+                model: model,
+                scope,
+                includeImages
             });
         }
         // Streaming yanıtları event listener'lar yönetiyor
@@ -1490,6 +1864,48 @@ async function handleChatSubmit() {
     }
 }
 
+function setIncludeImages(on) {
+    state.includeImages = on;
+    const btn = document.getElementById('include-images');
+    if (btn) btn.classList.toggle('active', on);
+}
+
+// Sayfadaki veri tablolarını backend'den CSV/JSON olarak alır ve dosya olarak indirir
+async function exportPageTables(format) {
+    if (!state.currentUrl) {
+        addChatMessage('system', 'Lütfen önce bir web sayfası açın!');
+        return;
+    }
+    if (!tauriInvoke) return;
+    updateChatStatus('Tablolar çıkarılıyor...');
+    try {
+        const tables = await tauriInvoke('export_page_tables', { url: state.currentUrl, format });
+        let host = 'sayfa';
+        try { host = new URL(state.currentUrl).hostname; } catch (_) {}
+        tables.forEach(t => {
+            const mime = format === 'csv' ? 'text/csv;charset=utf-8' : 'application/json';
+            // CSV'ye BOM eklenir; Excel UTF-8'i ancak böyle tanır
+            const body = format === 'csv' ? '\ufeff' + t.data : t.data;
+            const a = document.createElement('a');
+            a.href = URL.createObjectURL(new Blob([body], { type: mime }));
+            a.download = `${host}-tablo-${t.index}.${format}`;
+            document.body.appendChild(a);
+            a.click();
+            a.remove();
+            setTimeout(() => URL.revokeObjectURL(a.href), 1000);
+        });
+        // Tablo adları sayfadan geldiği için HTML olarak değil metin olarak eklenir
+        const escape = (str) => { const d = document.createElement('div'); d.textContent = str; return d.innerHTML; };
+        const list = tables.map(t => `${t.index}. ${escape(t.caption || 'Başlıksız')} (${t.rows}×${t.columns})`).join('<br>');
+        addChatMessage('system', `${tables.length} tablo ${format.toUpperCase()} olarak dışa aktarıldı:<br>${list}`);
+        updateChatStatus(`${tables.length} tablo dışa aktarıldı`);
+    } catch (e) {
+        console.error('Tablolar dışa aktarılamadı:', e);
+        addChatMessage('system', `Tablolar dışa aktarılamadı: ${e}`);
+        updateChatStatus('Hata oluştu', 'error');
+    }
+}
+
 function addChatMessage(type, content) {
     const messageDiv = document.createElement('div');
     messageDiv.className = `message ${type}`;
@@ -1,3 +1,66 @@
+// Uygulama komutları için allow-/deny- izinleri üretilir. Böylece her komut bir yetenek (capability)
+// tarafından açıkça izin verilmedikçe çağrılamaz; sekmelerdeki uzak sayfalar yalnızca tab-bridge
+// kümesindeki komutlara erişir (bkz. permissions/app.toml). Liste lib.rs içindeki generate_handler! ile
+// aynı tutulmalı; yeni komut ayrıca permissions/app.toml içindeki app-commands kümesine eklenir.
+const COMMANDS: &[&str] = &[
+  "get_ollama_models",
+  "get_openrouter_models",
+  "ask_question",
+  "ask_question_openrouter",
+  "get_popular_sites",
+  "save_popular_site",
+  "delete_popular_site",
+  "reorder_popular_sites",
+  "clear_cache_for_url",
+  "open_in_browser",
+  "open_or_navigate_browser_tab",
+  "navigate_back",
+  "navigate_forward",
+  "reload_page",
+  "reposition_webview",
+  "show_webview",
+  "hide_webview",
+  "show_only_tab",
+  "save_chat_message",
+  "load_chat_messages",
+  "get_ollama_base_url",
+  "set_ollama_base_url",
+  "get_page_info",
+  "notify_url_change",
+  "submit_live_dom",
+  "get_firecrawl_config",
+  "set_firecrawl_config",
+  "crawl_site",
+  "get_crawl_options",
+  "get_strategy_chains",
+  "set_strategy_chains",
+  "get_default_strategy_steps",
+  "get_race_options",
+  "set_race_options",
+  "get_polite_config",
+  "export_page_tables",
+  "feed_preview",
+  "set_polite_config",
+  "get_proxy_config",
+  "set_proxy_config",
+  "test_proxy",
+  "get_http_config",
+  "set_http_config",
+  "get_page_cache_config",
+  "set_page_cache_config",
+  "get_page_cache_stats",
+  "list_page_cache",
+  "purge_page_cache_domain",
+  "purge_page_cache",
+  "get_cookie_sharing",
+  "set_cookie_sharing",
+  "get_answer_language",
+  "set_answer_language",
+  "set_crawl_options",
+];
+
 fn main() {
-  tauri_build::build()
+  let attributes = tauri_build::Attributes::new()
+    .app_manifest(tauri_build::AppManifest::new().commands(COMMANDS));
+  tauri_build::try_build(attributes).expect("tauri-build başarısız");
 }
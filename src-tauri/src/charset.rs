@@ -0,0 +1,114 @@
+// Çekilen sayfaların karakter kodlamasını belirleyip UTF-8'e çevirir.
+// Sıra: Content-Type charset -> <meta charset> / http-equiv -> BOM -> istatistiksel tespit.
+// windows-1254 / ISO-8859-9 kullanan eski Türkçe siteler bu sayede bozuk karakter üretmez.
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8};
+use serde::Serialize;
+
+// Meta etiketleri için taranacak bayt sayısı (HTML standardı 1024 der, pratikte daha geç gelebiliyor)
+const META_SCAN_BYTES: usize = 4096;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EncodingInfo {
+    pub name: String,
+    // header | meta | bom | detected
+    pub via: String,
+    // Çözme sırasında geçersiz bayt dizisi ile karşılaşıldı mı?
+    pub had_errors: bool,
+}
+
+pub struct Decoded {
+    pub text: String,
+    pub encoding: EncodingInfo,
+}
+
+pub fn decode_html(bytes: &[u8], content_type: Option<&str>, url: Option<&str>) -> Decoded {
+    let (encoding, via) = if let Some(enc) = content_type.and_then(charset_from_content_type) {
+        (enc, "header")
+    } else if let Some(enc) = charset_from_meta(bytes) {
+        (enc, "meta")
+    } else if let Some((enc, _)) = Encoding::for_bom(bytes) {
+        (enc, "bom")
+    } else {
+        (detect(bytes, url), "detected")
+    };
+
+    // Bildirilen kodlama ile uyumlu BOM varsa çıkar
+    let body = match Encoding::for_bom(bytes) {
+        Some((bom_enc, len)) if bom_enc == encoding => &bytes[len..],
+        _ => bytes,
+    };
+    let (text, had_errors) = encoding.decode_without_bom_handling(body);
+    Decoded {
+        text: text.into_owned(),
+        encoding: EncodingInfo { name: encoding.name().to_string(), via: via.to_string(), had_errors },
+    }
+}
+
+// "text/html; charset=windows-1254" -> windows-1254
+pub fn charset_from_content_type(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (key, value) = param.split_once('=')?;
+        if !key.trim().eq_ignore_ascii_case("charset") { return None; }
+        label_to_encoding(value)
+    })
+}
+
+// <meta charset="..."> ve <meta http-equiv="Content-Type" content="...; charset=..."> tarar.
+fn charset_from_meta(bytes: &[u8]) -> Option<&'static Encoding> {
+    let head = &bytes[..bytes.len().min(META_SCAN_BYTES)];
+    // Baş kısım ASCII uyumlu kabul edilir; geçersiz baytlar meta aramasını etkilemez
+    let lower = String::from_utf8_lossy(head).to_lowercase();
+    let mut idx = 0usize;
+    while let Some(pos) = lower[idx..].find("<meta") {
+        let start = idx + pos;
+        let end = lower[start..].find('>').map(|p| start + p).unwrap_or(lower.len());
+        let tag = &lower[start..end];
+        // UTF-16 beyanı ASCII uyumlu bir meta etiketinde anlamsızdır; standart gereği UTF-8 kabul edilir
+        if let Some(value) = attr_value(tag, "charset") {
+            if let Some(enc) = label_to_encoding(&value) { return Some(enc.output_encoding()); }
+        }
+        if attr_value(tag, "http-equiv").map(|v| v == "content-type").unwrap_or(false) {
+            if let Some(content) = attr_value(tag, "content") {
+                if let Some(enc) = charset_from_content_type(&content) { return Some(enc.output_encoding()); }
+            }
+        }
+        idx = end;
+    }
+    None
+}
+
+fn attr_value(tag: &str, attr: &str) -> Option<String> {
+    let mut search = 0usize;
+    while let Some(pos) = tag[search..].find(attr) {
+        let at = search + pos;
+        search = at + attr.len();
+        // Öncesi boşluk olmalı (ör. "http-equiv" içindeki "equiv" eşleşmesin)
+        if at > 0 && !tag.as_bytes()[at - 1].is_ascii_whitespace() { continue; }
+        let rest = tag[search..].trim_start();
+        let rest = match rest.strip_prefix('=') { Some(r) => r.trim_start(), None => continue };
+        let value = match rest.chars().next() {
+            Some(q @ ('"' | '\'')) => rest[1..].split(q).next().unwrap_or(""),
+            _ => rest.split(|c: char| c.is_whitespace() || c == '/' || c == '>').next().unwrap_or(""),
+        };
+        return Some(value.trim().to_string());
+    }
+    None
+}
+
+fn label_to_encoding(label: &str) -> Option<&'static Encoding> {
+    let label = label.trim().trim_matches(|c| c == '"' || c == '\'');
+    Encoding::for_label(label.as_bytes())
+}
+
+// chardetng ile istatistiksel tahmin; alan adının TLD'si (örn. .tr) ipucu olarak verilir.
+fn detect(bytes: &[u8], url: Option<&str>) -> &'static Encoding {
+    if std::str::from_utf8(bytes).is_ok() { return UTF_8; }
+    let tld = url
+        .and_then(|u| url::Url::parse(u).ok())
+        .and_then(|u| u.host_str().and_then(|h| h.rsplit('.').next()).map(|t| t.to_lowercase()));
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    detector.guess(tld.as_deref().map(str::as_bytes), true)
+}
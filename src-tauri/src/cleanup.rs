@@ -0,0 +1,208 @@
+// Modele gitmeden önce içerik temizliği.
+// Her stratejinin (Firecrawl Markdown'ı dahil) çıktısı satır/paragraf düzeyinde elden geçirilir:
+// çerez/onay metinleri, bülten çağrıları, "ilgili yazılar" ve paylaş düğmesi etiketleri gibi bilinen
+// kalıplar, sayfada tekrar eden satırlar ve çoğu bağlantıdan oluşan bloklar atılır. Kod blokları ve
+// tablolar olduğu gibi kalır. Önce/sonra boyutları rapor olarak content-source olayına gider.
+
+use serde::Serialize;
+use std::collections::HashSet;
+
+// Kısa blok ya da satır, metninin büyük kısmını bilinen kalıplar oluşturuyorsa atılır ("Subscribe to
+// our newsletter!", çerez bandı). Kalıbın yalnızca geçtiği paragraf ("advertisement", "cookie policy"
+// üzerine bir makale) içerik sayılır.
+const MAX_BOILERPLATE_BLOCK: usize = 600;
+const MIN_PATTERN_SHARE: f64 = 0.4;
+// Bağlantı metninin blok metnine oranı bunun üstündeyse blok bağlantı listesi sayılır
+const MAX_LINK_DENSITY: f64 = 0.6;
+const MIN_LINKS_FOR_DENSITY: usize = 3;
+// Temizlik sonrası bundan az metin kalırsa temizlik geri alınır
+const MIN_REMAINING_CHARS: usize = 100;
+
+// Blok içinde geçtiğinde bloğu kalıp metin yapan ifadeler (küçük harf)
+const BOILERPLATE_PATTERNS: &[&str] = &[
+    // çerez / onay
+    "we use cookies", "this website uses cookies", "this site uses cookies", "accept all cookies", "accept cookies",
+    "cookie policy", "cookie settings", "manage cookies", "cookie preferences", "by continuing to use this site",
+    "privacy preferences", "çerez kullan", "çerezleri kabul", "çerez politikası", "çerez ayarları",
+    "wir verwenden cookies", "cookies akzeptieren", "cookie-einstellungen", "datenschutzeinstellungen",
+    // bülten / abonelik
+    "subscribe to our newsletter", "sign up for our newsletter", "sign up for the newsletter", "join our newsletter",
+    "enter your email address", "bültenimize abone", "e-bültene kaydol", "newsletter abonnieren",
+    "melden sie sich für unseren newsletter",
+    // ilgili içerik
+    "related articles", "related posts", "related stories", "you may also like", "you might also like",
+    "recommended for you", "more from our", "ilgili haberler", "ilgili yazılar", "benzer içerikler",
+    "bunlar da ilginizi", "das könnte sie auch interessieren", "mehr zum thema", "weitere artikel",
+    // paylaşım
+    "share this article", "share this post", "share on facebook", "share on twitter", "share on linkedin",
+    "bu haberi paylaş", "diesen artikel teilen",
+    // reklam
+    "advertisement", "sponsored content", "reklam alanı",
+];
+
+// Satırın tamamı bunlardan biriyse (paylaş düğmeleri vb.) satır atılır
+const BOILERPLATE_LABELS: &[&str] = &[
+    "share", "tweet", "facebook", "twitter", "x", "linkedin", "whatsapp", "telegram", "pinterest", "reddit",
+    "email", "e-mail", "e-posta", "print", "yazdır", "drucken", "copy link", "bağlantıyı kopyala", "paylaş",
+    "teilen", "pin it", "advertisement", "reklam", "anzeige", "werbung", "read more", "devamını oku",
+    "weiterlesen", "accept", "kabul et", "akzeptieren", "reject", "reddet", "ablehnen", "close", "kapat",
+    "schließen", "skip to content", "skip to main content", "içeriğe geç", "zum inhalt springen",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub before: usize, // bayt
+    pub after: usize,
+    pub removed_blocks: usize,
+    pub removed_lines: usize,
+    pub reverted: bool, // temizlik neredeyse her şeyi sildiği için uygulanmadı
+}
+
+pub fn clean(text: &str) -> (String, Report) {
+    let mut removed_blocks = 0;
+    let mut removed_lines = 0;
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut kept: Vec<String> = Vec::new();
+
+    for block in blocks(text) {
+        if block.verbatim {
+            kept.push(block.text);
+            continue;
+        }
+        if is_boilerplate_block(&block.text) || is_link_list(&block.text) {
+            removed_blocks += 1;
+            continue;
+        }
+        let mut lines: Vec<&str> = Vec::new();
+        for line in block.text.lines() {
+            let key = line_key(line);
+            if is_label(&key) || is_boilerplate_line(&key) {
+                removed_lines += 1;
+                continue;
+            }
+            // Aynı satır daha önce geçtiyse (menü, alt bilgi) yalnızca ilki kalır. Başlıklar ve tablo
+            // satırları hariç: başlık listesindeki ("Başlıklar:") bir madde asıl başlığı silmesin.
+            let structural = line.trim_start().starts_with(['|', '#']);
+            if key.chars().count() >= 4 && !structural && !seen.insert(key) {
+                removed_lines += 1;
+                continue;
+            }
+            lines.push(line);
+        }
+        if lines.iter().all(|l| l.trim().is_empty()) {
+            removed_blocks += 1;
+            continue;
+        }
+        kept.push(lines.join("\n"));
+    }
+
+    let cleaned = kept.join("\n\n");
+    let before = text.len();
+    if cleaned.trim().chars().count() < MIN_REMAINING_CHARS && text.trim().chars().count() >= MIN_REMAINING_CHARS {
+        let report = Report { before, after: before, removed_blocks: 0, removed_lines: 0, reverted: true };
+        return (text.to_string(), report);
+    }
+    let report = Report { before, after: cleaned.len(), removed_blocks, removed_lines, reverted: false };
+    (cleaned, report)
+}
+
+struct Block {
+    text: String,
+    verbatim: bool, // kod bloğu ya da tablo: dokunulmaz
+}
+
+// Boş satırlarla ayrılmış bloklar; ``` çitleri arasındaki satırlar tek blok kalır
+fn blocks(text: &str) -> Vec<Block> {
+    let mut out: Vec<Block> = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut in_fence = false;
+    let flush = |current: &mut Vec<&str>, out: &mut Vec<Block>, verbatim: bool| {
+        if current.is_empty() { return; }
+        let text = current.join("\n");
+        let verbatim = verbatim || current.iter().all(|l| l.trim_start().starts_with('|'));
+        out.push(Block { text, verbatim });
+        current.clear();
+    };
+    for line in text.lines() {
+        let fence = line.trim_start().starts_with("```");
+        if in_fence {
+            current.push(line);
+            if fence {
+                flush(&mut current, &mut out, true);
+                in_fence = false;
+            }
+        } else if fence {
+            flush(&mut current, &mut out, false);
+            current.push(line);
+            in_fence = true;
+        } else if line.trim().is_empty() {
+            flush(&mut current, &mut out, false);
+        } else {
+            current.push(line);
+        }
+    }
+    flush(&mut current, &mut out, in_fence);
+    out
+}
+
+// Karşılaştırma anahtarı: Markdown işaretleri ve fazla boşluk olmadan küçük harf
+fn line_key(line: &str) -> String {
+    let trimmed = line.trim().trim_start_matches(['#', '-', '*', '>', '+']).trim();
+    crate::readability::collapse_ws(trimmed).to_lowercase()
+}
+
+fn is_label(key: &str) -> bool {
+    let key = key.trim_matches(|c: char| !c.is_alphanumeric());
+    !key.is_empty() && BOILERPLATE_LABELS.contains(&key)
+}
+
+fn is_boilerplate_block(text: &str) -> bool {
+    text.len() <= MAX_BOILERPLATE_BLOCK && pattern_share(&text.to_lowercase()) >= MIN_PATTERN_SHARE
+}
+
+// key: line_key ile küçültülmüş satır
+fn is_boilerplate_line(key: &str) -> bool {
+    pattern_share(key) >= MIN_PATTERN_SHARE
+}
+
+// Kalıpların kapladığı harf/rakam payı (küçük harfli metinde)
+fn pattern_share(lower: &str) -> f64 {
+    let alnum = |s: &str| s.chars().filter(|c| c.is_alphanumeric()).count();
+    let total = alnum(lower);
+    if total == 0 { return 0.0; }
+    let matched: usize = BOILERPLATE_PATTERNS.iter().filter(|p| lower.contains(*p)).map(|p| alnum(p)).sum();
+    matched as f64 / total as f64
+}
+
+// [metin](adres) bağlantılarının görünür metne oranı
+fn is_link_list(text: &str) -> bool {
+    let mut links = 0;
+    let mut link_chars = 0;
+    let mut visible = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        visible.push_str(&rest[..open]);
+        let tail = &rest[open + 1..];
+        let Some(close) = tail.find("](") else {
+            visible.push('[');
+            rest = tail;
+            continue;
+        };
+        let Some(end) = tail[close + 2..].find(')') else {
+            visible.push('[');
+            rest = tail;
+            continue;
+        };
+        let label = &tail[..close];
+        links += 1;
+        link_chars += label.chars().filter(|c| c.is_alphanumeric()).count();
+        visible.push_str(label);
+        rest = &tail[close + 2 + end + 1..];
+    }
+    visible.push_str(rest);
+    if links < MIN_LINKS_FOR_DENSITY { return false; }
+    let total = visible.chars().filter(|c| c.is_alphanumeric()).count().max(1);
+    let linked = link_chars.min(total);
+    linked as f64 / total as f64 > MAX_LINK_DENSITY
+}
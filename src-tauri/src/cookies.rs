@@ -0,0 +1,61 @@
+// Oturum açılmış sayfalar için sekme çerezlerinin backend isteklerinde kullanılması.
+// Kullanıcı açıkça izin verdiği alan adı kalıplarını (strateji zinciriyle aynı biçim) listeler; yalnızca bu
+// sitelerde webview çerez deposundaki o adrese ait çerezler Cookie başlığı olarak sayfa isteğine eklenir.
+// Çerezler Firecrawl'a, çıkarıcılara ya da başka bir üçüncü tarafa hiçbir zaman gönderilmez.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{strategy, ChatStore};
+
+const KEY_COOKIES: &str = "cookie_domains";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CookieSharing {
+    // "example.com", "*.example.com"; "*" (tüm siteler) kabul edilmez
+    pub domains: Vec<String>,
+}
+
+impl CookieSharing {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_COOKIES)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        let mut domains: Vec<String> = Vec::new();
+        for d in &self.domains {
+            let d = d.trim().to_lowercase();
+            if d.is_empty() || domains.contains(&d) { continue; }
+            if d.trim_start_matches("*.").is_empty() || d.trim_start_matches("*.").contains(['*', '/', ' ', ':']) {
+                return Err(format!("Çerez paylaşımı için geçersiz alan adı: {} (örn. example.com ya da *.example.com)", d));
+            }
+            domains.push(d);
+        }
+        let raw = serde_json::to_string(&CookieSharing { domains }).map_err(|e| format!("Çerez ayarları serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_COOKIES, &raw)
+    }
+
+    pub fn allows(&self, url: &str) -> bool {
+        let Ok(u) = url::Url::parse(url) else { return false };
+        if !matches!(u.scheme(), "http" | "https") { return false; }
+        let host = u.host_str().unwrap_or("").to_lowercase();
+        self.domains.iter().any(|d| d != "*" && strategy::pattern_score(d, &host).is_some())
+    }
+}
+
+// Webview çerez deposundan adrese gönderilecek çerezler ("ad=değer; ad2=değer2"); çerez yoksa None.
+// Depo tüm sekmelerde ortak olduğundan herhangi bir sekme webview'i yeterlidir.
+pub fn header_for(webview: &tauri::Webview, url: &str) -> Result<Option<String>, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Geçersiz URL: {}", e))?;
+    let cookies = webview.cookies_for_url(parsed).map_err(|e| format!("Sekme çerezleri okunamadı: {}", e))?;
+    let header = cookies
+        .iter()
+        .map(|c| format!("{}={}", c.name(), c.value()))
+        .collect::<Vec<_>>()
+        .join("; ");
+    Ok((!header.is_empty()).then_some(header))
+}
@@ -0,0 +1,331 @@
+// Site tarama modu: tek sayfa yerine bir sitenin birden çok sayfasından derlem (corpus) oluşturur.
+// Sayfalar sitemap.xml ve sayfa içi bağlantılardan keşfedilir; derinlik ve sayfa sınırı uygulanır,
+// varsayılan olarak yalnızca aynı köken (scheme + host + port) taranır. İsteğe bağlı olarak
+// tarama Firecrawl'ın crawl uç noktasına devredilebilir. Her sayfa kaynak URL'si ile saklanır,
+// böylece model cevabında hangi bilginin hangi sayfadan geldiğini belirtebilir.
+
+use futures::StreamExt;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::time::Instant;
+
+use crate::{firecrawl, readability, ChatStore, FetchedBody};
+
+const KEY_OPTIONS: &str = "crawl_options";
+// Aynı anda çekilecek sayfa sayısı
+const CONCURRENCY: usize = 4;
+// Sitemap'ten en fazla bu kadar alt sitemap okunur
+const MAX_CHILD_SITEMAPS: usize = 3;
+
+// Taranmaya değmeyen dosya uzantıları
+const SKIP_EXTENSIONS: &[&str] = &[
+    ".jpg", ".jpeg", ".png", ".gif", ".webp", ".svg", ".ico", ".bmp", ".css", ".js", ".json",
+    ".xml", ".zip", ".gz", ".rar", ".7z", ".tar", ".mp3", ".mp4", ".webm", ".avi", ".mov",
+    ".woff", ".woff2", ".ttf", ".eot", ".exe", ".dmg", ".apk",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CrawlOptions {
+    pub max_depth: u32,
+    pub max_pages: usize,
+    pub same_origin: bool,
+    pub use_sitemap: bool,
+    pub backend: String, // local | firecrawl
+}
+
+impl Default for CrawlOptions {
+    fn default() -> Self {
+        Self { max_depth: 2, max_pages: 15, same_origin: true, use_sitemap: true, backend: "local".to_string() }
+    }
+}
+
+impl CrawlOptions {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_OPTIONS)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        self.validate()?;
+        let raw = serde_json::to_string(self).map_err(|e| format!("Tarama seçenekleri serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_OPTIONS, &raw)
+    }
+
+    // Kaydedilen ve crawl_site'a doğrudan verilen seçenekler için aynı sınırlar
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_pages == 0 || self.max_pages > 100 {
+            return Err("Sayfa sınırı 1 ile 100 arasında olmalı".to_string());
+        }
+        if self.max_depth > 5 {
+            return Err("Derinlik sınırı en fazla 5 olabilir".to_string());
+        }
+        if !matches!(self.backend.as_str(), "local" | "firecrawl") {
+            return Err(format!("Bilinmeyen tarama altyapısı: {}", self.backend));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorpusPage {
+    pub url: String,
+    pub title: Option<String>,
+    pub depth: u32,
+    pub source: String,
+    pub length: usize,
+    #[serde(skip)]
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SiteCorpus {
+    pub root: String,
+    pub backend: String,
+    pub pages: Vec<CorpusPage>,
+    #[serde(skip)]
+    pub built_at: Option<Instant>,
+}
+
+impl SiteCorpus {
+    pub fn source_label(&self) -> &'static str {
+        if self.backend == "firecrawl" { "site_firecrawl" } else { "site_crawl" }
+    }
+
+    // Tüm sayfaları bütçeye sığacak şekilde birleştirir. Her sayfa numaralı bir başlıkla
+    // ve URL'siyle başlar; bütçe sayfalara eşit bölünür, kısa sayfalardan artan pay sonrakilere kalır.
+    pub fn render(&self, budget: usize) -> String {
+        let mut out = format!(
+            "[Site derlemi] {} ({} sayfa)\nHer bölüm [Sayfa N] etiketi ve kaynak URL ile başlar; cevapta bilginin geldiği sayfayı bu etiketle belirt.\n\nSayfalar:\n",
+            self.root,
+            self.pages.len()
+        );
+        for (i, p) in self.pages.iter().enumerate() {
+            out.push_str(&format!("- [Sayfa {}] {} — {}\n", i + 1, p.title.as_deref().unwrap_or("(başlıksız)"), p.url));
+        }
+        out.push('\n');
+
+        let mut remaining = budget.saturating_sub(out.len());
+        for (i, p) in self.pages.iter().enumerate() {
+            let share = remaining / (self.pages.len() - i).max(1);
+            let header = format!("=== [Sayfa {}] {} ===\n", i + 1, p.url);
+            if share <= header.len() + 2 { break; }
+            let body = readability::truncate_utf8(p.content.trim(), share - header.len() - 2);
+            out.push_str(&header);
+            out.push_str(body);
+            out.push_str("\n\n");
+            remaining = remaining.saturating_sub(header.len() + body.len() + 2);
+        }
+        out
+    }
+}
+
+// Tarama ilerlemesi: (taranan sayfa, hedef sayfa sayısı, son URL)
+pub type Progress<'a> = &'a (dyn Fn(usize, usize, &str) + Send + Sync);
+
+pub async fn crawl_site(root: &str, opts: &CrawlOptions, fc: &firecrawl::FirecrawlConfig, progress: Progress<'_>) -> Result<SiteCorpus, String> {
+    let root_url = url::Url::parse(root).map_err(|e| format!("Geçersiz URL: {}", e))?;
+    if !matches!(root_url.scheme(), "http" | "https") {
+        return Err("Yalnızca http(s) siteleri taranabilir".to_string());
+    }
+
+    if opts.backend == "firecrawl" {
+        if fc.is_usable() {
+            match crawl_with_firecrawl(root, opts, fc).await {
+                Ok(corpus) => return Ok(corpus),
+                Err(e) => warn!("Firecrawl site taraması başarısız: {} - yerel tarayıcıya geçiliyor.", e),
+            }
+        } else {
+            warn!("Firecrawl kullanılamıyor - yerel tarayıcıya geçiliyor.");
+        }
+    }
+    crawl_locally(&root_url, opts, progress).await
+}
+
+async fn crawl_with_firecrawl(root: &str, opts: &CrawlOptions, fc: &firecrawl::FirecrawlConfig) -> Result<SiteCorpus, String> {
+    let client = crate::http::shared().client();
+    let docs = firecrawl::crawl_site(root, &client, fc, opts.max_pages, opts.max_depth).await?;
+    let pages = docs
+        .into_iter()
+        .take(opts.max_pages)
+        .map(|d| CorpusPage {
+            length: d.markdown.len(),
+            url: d.url,
+            title: d.title,
+            depth: 0, // Firecrawl derinlik bilgisi vermiyor
+            source: "firecrawl".to_string(),
+            content: d.markdown,
+        })
+        .collect();
+    Ok(SiteCorpus { root: root.to_string(), backend: "firecrawl".to_string(), pages, built_at: Some(Instant::now()) })
+}
+
+// Seviye seviye (BFS) tarama; her seviye sınırlı eşzamanlılıkla çekilir.
+async fn crawl_locally(root: &url::Url, opts: &CrawlOptions, progress: Progress<'_>) -> Result<SiteCorpus, String> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut pages: Vec<CorpusPage> = Vec::new();
+    let mut level: Vec<url::Url> = vec![normalize(root)];
+    seen.insert(normalize(root).to_string());
+
+    // Sitemap'teki adresler kök sayfanın bağlantıları gibi 1. seviyeye eklenir
+    let mut sitemap_urls: Vec<url::Url> = Vec::new();
+    if opts.use_sitemap && opts.max_depth > 0 {
+        sitemap_urls = sitemap_urls_for(root).await;
+        info!("sitemap.xml'den {} adres bulundu: {}", sitemap_urls.len(), root);
+    }
+
+    let mut depth = 0u32;
+    while !level.is_empty() && pages.len() < opts.max_pages {
+        let budget = opts.max_pages - pages.len();
+        let batch: Vec<url::Url> = level.drain(..).take(budget).collect();
+        let results: Vec<(url::Url, Result<FetchedPage, String>)> = futures::stream::iter(batch)
+            .map(|u| async move {
+                let r = fetch_page(&u).await;
+                (u, r)
+            })
+            .buffer_unordered(CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut next: Vec<url::Url> = Vec::new();
+        if depth == 0 {
+            for u in sitemap_urls.drain(..) {
+                if in_scope(root, &u, opts) && seen.insert(u.to_string()) { next.push(u); }
+            }
+        }
+        for (u, result) in results {
+            match result {
+                Ok(page) => {
+                    if depth < opts.max_depth {
+                        for link in page.links {
+                            let link = normalize(&link);
+                            if in_scope(root, &link, opts) && seen.insert(link.to_string()) { next.push(link); }
+                        }
+                    }
+                    if page.content.trim().len() > 100 {
+                        pages.push(CorpusPage {
+                            url: u.to_string(),
+                            title: page.title,
+                            depth,
+                            source: page.source.to_string(),
+                            length: page.content.len(),
+                            content: page.content,
+                        });
+                        progress(pages.len(), opts.max_pages, u.as_str());
+                    }
+                }
+                Err(e) => warn!("Sayfa taranamadı: {} ({})", u, e),
+            }
+            if pages.len() >= opts.max_pages { break; }
+        }
+        level = next;
+        depth += 1;
+    }
+
+    if pages.is_empty() {
+        return Err("Sitede taranabilir içerik bulunamadı".to_string());
+    }
+    // Kök sayfa her zaman başta, diğerleri derinliğe göre
+    pages.sort_by_key(|p| p.depth);
+    info!("Site taraması tamamlandı: {} ({} sayfa)", root, pages.len());
+    Ok(SiteCorpus { root: root.to_string(), backend: "local".to_string(), pages, built_at: Some(Instant::now()) })
+}
+
+struct FetchedPage {
+    title: Option<String>,
+    content: String,
+    source: &'static str,
+    links: Vec<url::Url>,
+}
+
+async fn fetch_page(u: &url::Url) -> Result<FetchedPage, String> {
+    match crate::http_get_body(u.as_str()).await? {
+        FetchedBody::Html(decoded) => {
+            let (title, links) = title_and_links(&decoded.text, u);
+            let (content, _) = crate::summarize_html(&decoded.text, u.as_str());
+            Ok(FetchedPage { title, content, source: "html", links })
+        }
+        FetchedBody::Pdf(bytes) => {
+            let page = crate::pdf_page(u.as_str(), bytes).await?;
+            Ok(FetchedPage { title: None, content: page.content, source: "pdf", links: Vec::new() })
+        }
+        FetchedBody::Feed(bytes) => {
+            let page = crate::feed_page(u.as_str(), &bytes)?;
+            Ok(FetchedPage { title: None, content: page.content, source: "feed", links: Vec::new() })
+        }
+    }
+}
+
+// DOM senkron ayrıştırılır (Html Send değil); bağlantılar sayfa adresine göre çözülür
+fn title_and_links(html: &str, base: &url::Url) -> (Option<String>, Vec<url::Url>) {
+    let doc = scraper::Html::parse_document(html);
+    let title = readability::document_title(&doc);
+    // <base href> varsa göreli bağlantılar ona göre çözülür
+    let base = scraper::Selector::parse("base[href]")
+        .ok()
+        .and_then(|sel| doc.select(&sel).next().and_then(|b| b.value().attr("href")).and_then(|h| base.join(h).ok()))
+        .unwrap_or_else(|| base.clone());
+    let mut links = Vec::new();
+    if let Ok(sel) = scraper::Selector::parse("a[href]") {
+        for a in doc.select(&sel) {
+            let rel = a.value().attr("rel").unwrap_or("").to_lowercase();
+            if rel.contains("nofollow") { continue; }
+            if let Some(u) = a.value().attr("href").and_then(|h| base.join(h.trim()).ok()) { links.push(u); }
+        }
+    }
+    (title, links)
+}
+
+fn normalize(u: &url::Url) -> url::Url {
+    let mut u = u.clone();
+    u.set_fragment(None);
+    u
+}
+
+fn in_scope(root: &url::Url, u: &url::Url, opts: &CrawlOptions) -> bool {
+    if !matches!(u.scheme(), "http" | "https") { return false; }
+    if opts.same_origin && u.origin() != root.origin() { return false; }
+    let path = u.path().to_lowercase();
+    !SKIP_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
+// /sitemap.xml (ve sitemap index ise ilk birkaç alt sitemap) içindeki <loc> adresleri
+async fn sitemap_urls_for(root: &url::Url) -> Vec<url::Url> {
+    let Ok(sitemap) = root.join("/sitemap.xml") else { return Vec::new() };
+    let Some(xml) = fetch_text(&sitemap).await else { return Vec::new() };
+    if xml.contains("<sitemapindex") {
+        let mut out = Vec::new();
+        for child in sitemap_locs(&xml).into_iter().take(MAX_CHILD_SITEMAPS) {
+            if let Some(child_xml) = fetch_text(&child).await { out.extend(sitemap_locs(&child_xml)); }
+        }
+        out
+    } else {
+        sitemap_locs(&xml)
+    }
+}
+
+async fn fetch_text(u: &url::Url) -> Option<String> {
+    match crate::http_get_body(u.as_str()).await {
+        Ok(FetchedBody::Html(decoded)) => Some(decoded.text),
+        _ => None,
+    }
+}
+
+fn sitemap_locs(xml: &str) -> Vec<url::Url> {
+    let mut out = Vec::new();
+    let mut idx = 0usize;
+    while let Some(pos) = xml[idx..].find("<loc>") {
+        let start = idx + pos + "<loc>".len();
+        let Some(end) = xml[start..].find("</loc>").map(|p| start + p) else { break };
+        let raw = xml[start..end].trim().trim_start_matches("<![CDATA[").trim_end_matches("]]>").replace("&amp;", "&");
+        if let Ok(u) = url::Url::parse(raw.trim()) { out.push(normalize(&u)); }
+        idx = end;
+    }
+    out
+}
@@ -0,0 +1,30 @@
+// Akış adresi gibi görünen yollar (/feed, /rss, .rss, atom.xml, feed.json ...) Firecrawl'a gönderilmeden
+// doğrudan indirilip son kayıtların listesine çevrilir. Diğer akışlar genel akışta
+// Content-Type / kök öğe kontrolüyle yakalanır.
+
+use async_trait::async_trait;
+
+use super::ContentExtractor;
+use crate::{feed_page, http_get_body, FetchedBody, ScrapedPage};
+
+const FEED_SUFFIXES: &[&str] = &[".rss", ".atom", "/feed", "/rss", "/atom", "rss.xml", "atom.xml", "feed.xml", "index.xml", "feed.json"];
+
+pub struct FeedExtractor;
+
+#[async_trait]
+impl ContentExtractor for FeedExtractor {
+    fn name(&self) -> &'static str { "feed" }
+
+    fn matches(&self, url: &url::Url) -> bool {
+        let path = url.path().to_lowercase();
+        let path = path.trim_end_matches('/');
+        FEED_SUFFIXES.iter().any(|s| path.ends_with(s))
+    }
+
+    async fn extract(&self, url: &str) -> Result<ScrapedPage, String> {
+        match http_get_body(url).await? {
+            FetchedBody::Feed(bytes) => feed_page(url, &bytes),
+            _ => Err("Adres akış gibi görünüyor ancak yanıt RSS/Atom/JSON Feed değil".to_string()),
+        }
+    }
+}
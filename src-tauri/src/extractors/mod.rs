@@ -0,0 +1,79 @@
+// Siteye özel içerik çıkarıcılar.
+// `scrape_page_content` genel Firecrawl/HTTP zincirine geçmeden önce bu kayıt defterine
+// öncelik sırasıyla sorar; URL'yi ilk eşleyen ve başarılı olan çıkarıcının sonucu kullanılır.
+// Yeni bir site (Wikipedia, GitHub, iç wiki vb.) için ContentExtractor yazıp
+// `ExtractorRegistry::with_defaults` içine eklemek yeterli.
+
+use async_trait::async_trait;
+use log::{info, warn};
+use std::sync::OnceLock;
+
+use crate::ScrapedPage;
+
+mod feed;
+mod pdf;
+mod youtube;
+
+#[async_trait]
+pub trait ContentExtractor: Send + Sync {
+    // Log ve hata mesajlarında görünen kısa ad
+    fn name(&self) -> &'static str;
+
+    // Büyük olan önce denenir
+    fn priority(&self) -> i32 { 0 }
+
+    fn matches(&self, url: &url::Url) -> bool;
+
+    // Başarılıysa içerik + kaynak etiketi (ScrapedPage.source) döner
+    async fn extract(&self, url: &str) -> Result<ScrapedPage, String>;
+}
+
+pub struct ExtractorRegistry {
+    extractors: Vec<Box<dyn ContentExtractor>>,
+}
+
+impl ExtractorRegistry {
+    pub fn new() -> Self {
+        Self { extractors: Vec::new() }
+    }
+
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(youtube::YoutubeExtractor));
+        registry.register(Box::new(pdf::PdfExtractor));
+        registry.register(Box::new(feed::FeedExtractor));
+        registry
+    }
+
+    pub fn register(&mut self, extractor: Box<dyn ContentExtractor>) {
+        self.extractors.push(extractor);
+        // Kararlı sıralama: aynı öncelikte kayıt sırası korunur
+        self.extractors.sort_by_key(|e| std::cmp::Reverse(e.priority()));
+    }
+
+    // Eşleşen çıkarıcıları sırayla dener. Hiçbiri eşleşmezse None (genel akışa devam),
+    // eşleşenlerin hepsi başarısız olursa hataları birleştirilmiş Some(Err) döner.
+    pub async fn extract(&self, url: &str) -> Option<Result<ScrapedPage, String>> {
+        let parsed = url::Url::parse(url).ok()?;
+        let mut errors = Vec::new();
+        for extractor in self.extractors.iter().filter(|e| e.matches(&parsed)) {
+            match extractor.extract(url).await {
+                Ok(page) => {
+                    info!("'{}' çıkarıcısı başarılı: {} ({} char)", extractor.name(), url, page.content.len());
+                    return Some(Ok(page));
+                }
+                Err(e) => {
+                    warn!("'{}' çıkarıcısı başarısız: {} - sonraki adıma geçiliyor.", extractor.name(), e);
+                    errors.push(format!("{}: {}", extractor.name(), e));
+                }
+            }
+        }
+        if errors.is_empty() { None } else { Some(Err(errors.join("; "))) }
+    }
+}
+
+static REGISTRY: OnceLock<ExtractorRegistry> = OnceLock::new();
+
+pub fn registry() -> &'static ExtractorRegistry {
+    REGISTRY.get_or_init(ExtractorRegistry::with_defaults)
+}
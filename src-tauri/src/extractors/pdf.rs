@@ -0,0 +1,25 @@
+// Yolu .pdf ile biten adresler Firecrawl'a gönderilmeden doğrudan indirilip ayrıştırılır.
+// Uzantısı olmayan PDF'ler genel akışta Content-Type / %PDF- imzasıyla yakalanır.
+
+use async_trait::async_trait;
+
+use super::ContentExtractor;
+use crate::{http_get_body, pdf_page, FetchedBody, ScrapedPage};
+
+pub struct PdfExtractor;
+
+#[async_trait]
+impl ContentExtractor for PdfExtractor {
+    fn name(&self) -> &'static str { "pdf" }
+
+    fn matches(&self, url: &url::Url) -> bool {
+        url.path().to_lowercase().ends_with(".pdf")
+    }
+
+    async fn extract(&self, url: &str) -> Result<ScrapedPage, String> {
+        match http_get_body(url).await? {
+            FetchedBody::Pdf(bytes) => pdf_page(url, bytes).await,
+            _ => Err("Adres .pdf ile bitiyor ancak yanıt PDF değil".to_string()),
+        }
+    }
+}
@@ -0,0 +1,333 @@
+// YouTube sayfaları Firecrawl tarafından çoğunlukla engelleniyor.
+// Bunun yerine oEmbed + og:meta etiketlerinden özet çıkarılır; izleme sayfasındaki
+// player response içinden altyazı listesi okunup zaman damgalı transkript eklenir.
+
+use async_trait::async_trait;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use super::ContentExtractor;
+use crate::http::Profile;
+use crate::{extract_attr_value, find_meta_property, polite, ScrapedPage};
+
+// Altyazı dili tercih sırası (önce elle yazılmış, sonra otomatik altyazılar bu sırayla denenir)
+const PREFERRED_CAPTION_LANGS: &[&str] = &["tr", "en"];
+// Transkript satırları bu süre aralıklarıyla paragraflara toplanır
+const TRANSCRIPT_CHUNK_SECS: u64 = 30;
+
+pub struct YoutubeExtractor;
+
+#[async_trait]
+impl ContentExtractor for YoutubeExtractor {
+    fn name(&self) -> &'static str { "youtube" }
+
+    fn matches(&self, url: &url::Url) -> bool {
+        is_youtube_url(url)
+    }
+
+    async fn extract(&self, url: &str) -> Result<ScrapedPage, String> {
+        scrape_youtube(url).await
+    }
+}
+
+fn is_youtube_url(url: &url::Url) -> bool {
+    match url.host_str() {
+        Some(host) => {
+            let h = host.to_lowercase();
+            h == "youtube.com" || h.ends_with(".youtube.com") || h == "youtu.be"
+        }
+        None => false,
+    }
+}
+
+// watch?v=ID, youtu.be/ID, /shorts/ID, /embed/ID, /live/ID
+fn video_id(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    let segments: Vec<&str> = parsed.path_segments().map(|s| s.filter(|p| !p.is_empty()).collect()).unwrap_or_default();
+    let id = if host == "youtu.be" {
+        segments.first().map(|s| s.to_string())
+    } else if let Some((_, v)) = parsed.query_pairs().find(|(k, _)| k == "v") {
+        Some(v.into_owned())
+    } else {
+        match segments.as_slice() {
+            ["shorts" | "embed" | "live" | "v", id, ..] => Some(id.to_string()),
+            _ => None,
+        }
+    }?;
+    let valid = !id.is_empty() && id.len() <= 20 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid { Some(id) } else { None }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct YoutubeOEmbed {
+    title: Option<String>,
+    author_name: Option<String>,
+    author_url: Option<String>,
+    thumbnail_url: Option<String>,
+}
+
+// Player response içindeki captionTracks girdisi
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptionTrack {
+    base_url: String,
+    language_code: String,
+    #[serde(default)]
+    kind: Option<String>, // "asr" = otomatik oluşturulmuş
+}
+
+impl CaptionTrack {
+    fn is_auto(&self) -> bool { self.kind.as_deref() == Some("asr") }
+}
+
+// timedtext fmt=json3 yanıtı
+#[derive(Debug, Deserialize)]
+struct TimedTextJson { #[serde(default)] events: Vec<TimedTextEvent> }
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimedTextEvent {
+    #[serde(default)]
+    t_start_ms: u64,
+    #[serde(default)]
+    segs: Option<Vec<TimedTextSeg>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TimedTextSeg { #[serde(default)] utf8: String }
+
+struct Transcript {
+    language: String,
+    auto_generated: bool,
+    // (başlangıç saniyesi, metin)
+    lines: Vec<(u64, String)>,
+}
+
+async fn scrape_youtube(url: &str) -> Result<ScrapedPage, String> {
+    // 1) OEmbed ile başlık/kanal bilgisi al
+    let polite = polite::politeness();
+
+    let oembed_url = format!("https://www.youtube.com/oembed?url={}&format=json", url);
+    let mut title: Option<String> = None;
+    let mut author: Option<String> = None;
+
+    // OEmbed isteğe bağlı; robots.txt engellerse yalnızca başlık bilgisi eksik kalır
+    let oembed = match polite.admit(&oembed_url).await {
+        Ok(()) => polite.request(&oembed_url, Profile::Metadata).send().await.ok(),
+        Err(e) => { warn!("{}", e); None }
+    };
+    if let Some(resp) = oembed {
+        if resp.status().is_success() {
+            if let Ok(text) = resp.text().await {
+                if let Ok(oe) = serde_json::from_str::<YoutubeOEmbed>(&text) {
+                    title = oe.title;
+                    author = oe.author_name;
+                }
+            }
+        }
+    }
+
+    // 2) Sayfa HTML'inden meta başlık/açıklama ve player response çek
+    polite.admit(url).await?;
+    let (desc, og_title, site_name, player) = match polite
+        .request(url, Profile::Scraper)
+        .header("Accept-Language", "tr-TR,tr;q=0.9,en;q=0.8")
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => {
+            match resp.text().await {
+                Ok(html) => {
+                    let d = find_meta_og_description(&html);
+                    let t = find_meta_property(&html, "og:title");
+                    let s = find_meta_property(&html, "og:site_name");
+                    let p = find_player_response(&html);
+                    (d, t, s, p)
+                },
+                Err(_) => (None, None, None, None),
+            }
+        }
+        _ => (None, None, None, None),
+    };
+
+    let details = player.as_ref().and_then(|p| p.get("videoDetails"));
+    let vid = video_id(url).or_else(|| details.and_then(|d| d["videoId"].as_str()).map(|s| s.to_string()));
+    // Açıklamanın tamamı player response'ta; og:description kısaltılmış olabilir
+    let full_desc = details.and_then(|d| d["shortDescription"].as_str()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    let length_secs = details.and_then(|d| d["lengthSeconds"].as_str()).and_then(|s| s.parse::<u64>().ok());
+
+    // 3) Altyazı izini seç ve indir
+    let transcript = match player.as_ref().map(caption_tracks).and_then(|tracks| pick_caption_track(&tracks)) {
+        Some(track) => match fetch_transcript(&track).await {
+            Ok(t) => Some(t),
+            Err(e) => { warn!("YouTube transkripti alınamadı: {}", e); None }
+        },
+        None => { info!("YouTube videosunda altyazı bulunamadı: {}", url); None }
+    };
+
+    // 4) Markdown çıktısı oluştur
+    let mut out = String::new();
+    out.push_str("[YouTube Video]\n\n");
+    if let Some(t) = title.clone().or(og_title.clone()) { out.push_str(&format!("Başlık: {}\n", t)); }
+    if let Some(a) = author.clone() { out.push_str(&format!("Kanal: {}\n", a)); }
+    if author.is_none() {
+        if let Some(sn) = site_name.clone() { out.push_str(&format!("Site: {}\n", sn)); }
+    }
+    if let Some(secs) = length_secs { out.push_str(&format!("Süre: {}\n", format_timestamp(secs))); }
+    out.push_str(&format!("URL: {}\n\n", url));
+    if let Some(d) = full_desc.or(desc) {
+        out.push_str("Açıklama:\n");
+        out.push_str(&d);
+        out.push_str("\n\n");
+    }
+
+    let source = match (&transcript, &vid) {
+        (Some(t), Some(id)) if !t.lines.is_empty() => {
+            out.push_str(&format!(
+                "Transkript ({}{}):\n",
+                t.language,
+                if t.auto_generated { ", otomatik altyazı" } else { "" }
+            ));
+            out.push_str("Her paragraf [dk:sn] zaman damgası ile başlar; damga videonun o anına bağlantıdır ve alıntı kaynağı olarak kullanılabilir.\n\n");
+            out.push_str(&render_transcript(&t.lines, id));
+            "youtube_transcript"
+        }
+        _ => "youtube_light",
+    };
+
+    if out.trim().is_empty() {
+        Err("YouTube içeriği çıkarılamadı".to_string())
+    } else {
+        Ok(ScrapedPage::new(out, source))
+    }
+}
+
+// `ytInitialPlayerResponse = {...};` atamasındaki JSON nesnesini ayrıştırır
+fn find_player_response(html: &str) -> Option<serde_json::Value> {
+    let marker = "ytInitialPlayerResponse";
+    let mut idx = 0usize;
+    while let Some(pos) = html[idx..].find(marker) {
+        let after = idx + pos + marker.len();
+        idx = after;
+        let rest = html[after..].trim_start();
+        let rest = match rest.strip_prefix('=') { Some(r) => r.trim_start(), None => continue };
+        if !rest.starts_with('{') { continue; }
+        // Akış ayrıştırıcısı ilk değeri okuyup durur; arkadaki `;var ...` kısmı sorun olmaz
+        if let Some(Ok(value)) = serde_json::Deserializer::from_str(rest).into_iter::<serde_json::Value>().next() {
+            return Some(value);
+        }
+    }
+    None
+}
+
+fn caption_tracks(player: &serde_json::Value) -> Vec<CaptionTrack> {
+    player
+        .pointer("/captions/playerCaptionsTracklistRenderer/captionTracks")
+        .and_then(|v| serde_json::from_value::<Vec<CaptionTrack>>(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+// Önce tercih edilen dillerde elle yazılmış, sonra herhangi bir elle yazılmış,
+// en son otomatik altyazı (yine tercih edilen dil öncelikli) seçilir.
+fn pick_caption_track(tracks: &[CaptionTrack]) -> Option<CaptionTrack> {
+    let lang_matches = |t: &CaptionTrack, lang: &str| {
+        t.language_code == lang || t.language_code.starts_with(&format!("{}-", lang))
+    };
+    for auto in [false, true] {
+        let pool: Vec<&CaptionTrack> = tracks.iter().filter(|t| t.is_auto() == auto).collect();
+        for lang in PREFERRED_CAPTION_LANGS {
+            if let Some(t) = pool.iter().find(|t| lang_matches(t, lang)) { return Some((*t).clone()); }
+        }
+        if let Some(t) = pool.first() { return Some((*t).clone()); }
+    }
+    None
+}
+
+async fn fetch_transcript(track: &CaptionTrack) -> Result<Transcript, String> {
+    let mut timedtext_url = url::Url::parse(&track.base_url).map_err(|e| format!("Geçersiz altyazı URL'si: {}", e))?;
+    // json3 biçimi olay başına başlangıç zamanı ve metin parçaları verir
+    let pairs: Vec<(String, String)> = timedtext_url.query_pairs().filter(|(k, _)| k != "fmt").map(|(k, v)| (k.into_owned(), v.into_owned())).collect();
+    timedtext_url.query_pairs_mut().clear().extend_pairs(pairs).append_pair("fmt", "json3");
+
+    let resp = polite::get(timedtext_url.as_str(), Profile::Scraper).await.map_err(|e| format!("timedtext isteği başarısız: {}", e))?;
+    if !resp.status().is_success() {
+        return Err(format!("timedtext HTTP {}", resp.status()));
+    }
+    let text = resp.text().await.map_err(|e| format!("timedtext yanıtı okunamadı: {}", e))?;
+    if text.trim().is_empty() {
+        return Err("timedtext yanıtı boş".to_string());
+    }
+    let parsed: TimedTextJson = serde_json::from_str(&text).map_err(|e| format!("timedtext JSON parse hatası: {}", e))?;
+
+    let lines: Vec<(u64, String)> = parsed
+        .events
+        .into_iter()
+        .filter_map(|ev| {
+            let text: String = ev.segs?.into_iter().map(|s| s.utf8).collect();
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            if text.is_empty() { None } else { Some((ev.t_start_ms / 1000, text)) }
+        })
+        .collect();
+    if lines.is_empty() {
+        return Err("Altyazı izi boş".to_string());
+    }
+    info!("YouTube transkripti alındı: {} ({} satır, otomatik: {})", track.language_code, lines.len(), track.is_auto());
+    Ok(Transcript { language: track.language_code.clone(), auto_generated: track.is_auto(), lines })
+}
+
+// Satırları TRANSCRIPT_CHUNK_SECS'lik paragraflara toplar; her paragraf videonun
+// ilgili saniyesine giden bir bağlantı ile başlar: [01:23](https://youtu.be/ID?t=83)
+fn render_transcript(lines: &[(u64, String)], video_id: &str) -> String {
+    let mut out = String::new();
+    let mut chunk_start: Option<u64> = None;
+    let mut chunk = String::new();
+    let flush = |out: &mut String, start: u64, chunk: &str| {
+        out.push_str(&format!("[{}](https://youtu.be/{}?t={}) {}\n\n", format_timestamp(start), video_id, start, chunk.trim()));
+    };
+    for (start, text) in lines {
+        match chunk_start {
+            Some(s) if start.saturating_sub(s) < TRANSCRIPT_CHUNK_SECS => {}
+            Some(s) => {
+                flush(&mut out, s, &chunk);
+                chunk.clear();
+                chunk_start = Some(*start);
+            }
+            None => chunk_start = Some(*start),
+        }
+        chunk.push_str(text);
+        chunk.push(' ');
+    }
+    if let Some(s) = chunk_start { flush(&mut out, s, &chunk); }
+    out
+}
+
+// 83 -> 01:23, 3723 -> 1:02:03
+fn format_timestamp(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if h > 0 { format!("{}:{:02}:{:02}", h, m, s) } else { format!("{:02}:{:02}", m, s) }
+}
+
+fn find_meta_og_description(html: &str) -> Option<String> {
+    // Basit dize arama: property="og:description" ve content="..."
+    let key = "property=\"og:description\"";
+    let key_alt = "property='og:description'";
+    if let Some(idx) = html.find(key).or_else(|| html.find(key_alt)) {
+        // Tag başlangıcını geriye doğru ara
+        let start = html[..idx].rfind("<meta").unwrap_or(0);
+        let end = html[idx..].find('>').map(|p| idx + p).unwrap_or(html.len());
+        let segment = &html[start..end];
+        // content="..." değerini al
+        if let Some(val) = extract_attr_value(segment, "content") { return Some(val); }
+    }
+    // Alternatif: name="description"
+    let key2 = "name=\"description\"";
+    let key2_alt = "name='description'";
+    if let Some(idx) = html.find(key2).or_else(|| html.find(key2_alt)) {
+        let start = html[..idx].rfind("<meta").unwrap_or(0);
+        let end = html[idx..].find('>').map(|p| idx + p).unwrap_or(html.len());
+        let segment = &html[start..end];
+        if let Some(val) = extract_attr_value(segment, "content") { return Some(val); }
+    }
+    None
+}
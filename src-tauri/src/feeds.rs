@@ -0,0 +1,175 @@
+// RSS / Atom / JSON Feed desteği.
+// Sayfadaki <link rel="alternate"> akışları keşfedilir, akış RSS 2.0 / Atom / JSON Feed olarak
+// ayrıştırılır ve son N kayıt (başlık, tarih, özet, bağlantı) okunabilir bir listeye çevrilir.
+// Doğrudan açılan akış adresleri de ham XML yerine aynı liste olarak gösterilir.
+
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+use crate::readability;
+
+// Modele verilecek / sekmede gösterilecek kayıt sayısı
+pub const DEFAULT_ENTRIES: usize = 15;
+// Kayıt özetleri bu uzunlukta kesilir
+const SUMMARY_CHARS: usize = 400;
+
+const FEED_TYPES: &[&str] = &["application/rss+xml", "application/atom+xml", "application/feed+json", "application/rdf+xml"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedLink {
+    pub url: String,
+    pub title: Option<String>,
+    pub media_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeedEntry {
+    pub title: String,
+    pub date: Option<String>, // RFC 3339
+    pub summary: Option<String>,
+    pub link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Feed {
+    pub url: String,
+    pub kind: &'static str, // rss | atom | json
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub site: Option<String>,
+    pub entries: Vec<FeedEntry>,
+}
+
+// <link rel="alternate" type="application/rss+xml" href="..."> vb.
+pub fn discover(html: &str, page_url: &str) -> Vec<FeedLink> {
+    let Ok(base) = url::Url::parse(page_url) else { return Vec::new() };
+    let doc = Html::parse_document(html);
+    let Ok(sel) = Selector::parse("link[rel][href][type]") else { return Vec::new() };
+    let mut out: Vec<FeedLink> = Vec::new();
+    for link in doc.select(&sel) {
+        let v = link.value();
+        let rel = v.attr("rel").unwrap_or("").to_lowercase();
+        if !rel.split_whitespace().any(|r| r == "alternate") { continue; }
+        let media_type = v.attr("type").unwrap_or("").trim().to_lowercase();
+        if !FEED_TYPES.contains(&media_type.as_str()) { continue; }
+        let Some(url) = v.attr("href").and_then(|h| base.join(h.trim()).ok()) else { continue };
+        if out.iter().any(|f| f.url == url.as_str()) { continue; }
+        let title = v.attr("title").map(readability::collapse_ws).filter(|t| !t.is_empty());
+        out.push(FeedLink { url: url.to_string(), title, media_type });
+    }
+    // Yorum akışları ("Comments Feed", "Yorum beslemesi") ana akıştan sonra gelsin
+    out.sort_by_key(|f| f.title.as_deref().map(|t| t.to_lowercase().contains("comment") || t.to_lowercase().contains("yorum")).unwrap_or(false));
+    out
+}
+
+// Content-Type ya da kök öğe akış mı? Sitemap gibi diğer XML belgeleri akış sayılmaz.
+pub fn looks_like_feed(content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let mime = content_type.and_then(|ct| ct.split(';').next()).map(|m| m.trim().to_lowercase()).unwrap_or_default();
+    if FEED_TYPES.contains(&mime.as_str()) { return true; }
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(2048)]).to_lowercase();
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    if head.starts_with('{') {
+        return head.contains("jsonfeed.org/version");
+    }
+    if !(head.starts_with('<') && (mime.is_empty() || mime.contains("xml") || mime == "text/plain")) { return false; }
+    // XML bildirimi, yorum ve stylesheet talimatlarından sonraki ilk öğe
+    head.split('<')
+        .filter(|t| !t.is_empty() && !t.starts_with('?') && !t.starts_with('!'))
+        .map(|t| t.split(|c: char| c.is_whitespace() || c == '>' || c == '/').next().unwrap_or(""))
+        .next()
+        .map(|root| matches!(root, "rss" | "feed" | "rdf:rdf"))
+        .unwrap_or(false)
+}
+
+pub fn parse(bytes: &[u8], url: &str, limit: usize) -> Result<Feed, String> {
+    let parsed = feed_rs::parser::parse(bytes).map_err(|e| format!("Akış ayrıştırılamadı: {}", e))?;
+    let kind = match parsed.feed_type {
+        feed_rs::model::FeedType::Atom => "atom",
+        feed_rs::model::FeedType::JSON => "json",
+        _ => "rss",
+    };
+    let site = parsed
+        .links
+        .iter()
+        .find(|l| l.rel.as_deref().map(|r| r == "alternate").unwrap_or(true) && !l.href.ends_with(".xml"))
+        .map(|l| l.href.clone());
+
+    let mut entries: Vec<(Option<chrono::DateTime<chrono::Utc>>, FeedEntry)> = parsed
+        .entries
+        .into_iter()
+        .map(|e| {
+            let date = e.published.or(e.updated);
+            let summary = e
+                .summary
+                .map(|t| t.content)
+                .or_else(|| e.content.and_then(|c| c.body))
+                .map(|s| plain_text(&s))
+                .filter(|s| !s.is_empty())
+                .map(|s| readability::truncate_chars(&s, SUMMARY_CHARS));
+            let link = e
+                .links
+                .iter()
+                .find(|l| l.rel.as_deref().map(|r| r == "alternate").unwrap_or(true))
+                .or_else(|| e.links.first())
+                .map(|l| l.href.clone());
+            let title = e.title.map(|t| plain_text(&t.content)).filter(|t| !t.is_empty()).unwrap_or_else(|| "(başlıksız)".to_string());
+            (date, FeedEntry { title, date: date.map(|d| d.to_rfc3339()), summary, link })
+        })
+        .collect();
+    // Akışlar genelde yeniden eskiye sıralıdır ama garanti değil; tarihi olmayanlar sona
+    entries.sort_by_key(|e| std::cmp::Reverse(e.0));
+    let entries: Vec<FeedEntry> = entries.into_iter().take(limit).map(|(_, e)| e).collect();
+    if entries.is_empty() {
+        return Err("Akışta kayıt yok".to_string());
+    }
+
+    Ok(Feed {
+        url: url.to_string(),
+        kind,
+        title: parsed.title.map(|t| plain_text(&t.content)).filter(|t| !t.is_empty()),
+        description: parsed.description.map(|t| plain_text(&t.content)).filter(|t| !t.is_empty()),
+        site,
+        entries,
+    })
+}
+
+impl Feed {
+    // Model bağlamı için Markdown liste
+    pub fn render(&self) -> String {
+        let mut out = format!("[Akış ({})] {}\n", self.kind.to_uppercase(), self.title.as_deref().unwrap_or(&self.url));
+        if let Some(desc) = &self.description { out.push_str(&format!("{}\n", desc)); }
+        out.push_str(&format!("Kaynak: {}\nSon {} kayıt (yeniden eskiye):\n\n", self.url, self.entries.len()));
+        for (i, e) in self.entries.iter().enumerate() {
+            out.push_str(&format!("{}. **{}**", i + 1, e.title));
+            if let Some(date) = e.date.as_deref().and_then(|d| d.get(..10)) { out.push_str(&format!(" — {}", date)); }
+            out.push('\n');
+            if let Some(summary) = &e.summary { out.push_str(&format!("   {}\n", summary)); }
+            if let Some(link) = &e.link { out.push_str(&format!("   {}\n", link)); }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+// "Bu blogda neler yeni?" türü kısa sorular akış bağlamıyla cevaplanır. Yalnızca siteye yeni
+// eklenenleri soran kalıplar ve kısa sorular: sayfa hakkındaki sıradan sorular ("makale en son neyi
+// söylüyor") sayfa bağlamında kalır. Diğer durumlarda /yeni komutu kullanılır.
+pub fn is_whats_new_question(question: &str) -> bool {
+    const HINTS: &[&str] = &[
+        "neler yeni", "yeni ne var", "son yazılar", "son gönderiler", "yeni yazılar",
+        "what's new", "whats new", "latest posts", "recent posts", "new posts",
+    ];
+    const MAX_WORDS: usize = 8;
+    let q = question.to_lowercase();
+    q.split_whitespace().count() <= MAX_WORDS && HINTS.iter().any(|h| q.contains(h))
+}
+
+// Özet/başlıklardaki HTML etiketlerini ve varlıkları temizler
+fn plain_text(s: &str) -> String {
+    if !s.contains('<') && !s.contains('&') { return readability::collapse_ws(s); }
+    let frag = Html::parse_fragment(s);
+    readability::collapse_ws(&frag.root_element().text().collect::<String>())
+}
@@ -0,0 +1,377 @@
+// Firecrawl istemcisi: adres, API sürümü (v0 / v1) ve scrape seçenekleri app_settings'ten okunur.
+// Varsayılan SaaS (api.firecrawl.dev) yerine yerel bir Firecrawl container'ı da kullanılabilir.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::{markdown, ChatStore};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.firecrawl.dev";
+
+const KEY_BASE_URL: &str = "firecrawl_base_url";
+const KEY_API_VERSION: &str = "firecrawl_api_version";
+const KEY_API_KEY: &str = "firecrawl_api_key";
+const KEY_OPTIONS: &str = "firecrawl_options";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FirecrawlOptions {
+    pub only_main_content: bool,
+    pub wait_for: Option<u64>,   // ms
+    pub formats: Vec<String>,    // v1: markdown | html | rawHtml | links ...
+    pub include_tags: Vec<String>,
+    pub exclude_tags: Vec<String>,
+    pub timeout: Option<u64>,    // ms
+}
+
+impl Default for FirecrawlOptions {
+    fn default() -> Self {
+        Self {
+            only_main_content: true,
+            wait_for: None,
+            formats: vec!["markdown".to_string()],
+            include_tags: Vec::new(),
+            exclude_tags: Vec::new(),
+            timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirecrawlConfig {
+    pub base_url: String,
+    pub api_version: String, // v0 | v1
+    // Boşsa: SaaS adresinde gömülü anahtar, kendi sunucunda kimlik doğrulamasız istek
+    pub api_key: Option<String>,
+    pub options: FirecrawlOptions,
+}
+
+impl FirecrawlConfig {
+    pub fn load(store: &ChatStore) -> Self {
+        let get = |key: &str| store.get_setting(key).unwrap_or(None).filter(|v| !v.trim().is_empty());
+        let options = get(KEY_OPTIONS)
+            .and_then(|raw| match serde_json::from_str::<FirecrawlOptions>(&raw) {
+                Ok(o) => Some(o),
+                Err(e) => { warn!("firecrawl_options okunamadı, varsayılanlar kullanılıyor: {}", e); None }
+            })
+            .unwrap_or_default();
+        Self {
+            base_url: get(KEY_BASE_URL).unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            api_version: get(KEY_API_VERSION).unwrap_or_else(|| "v0".to_string()),
+            api_key: get(KEY_API_KEY),
+            options,
+        }
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        let base = self.base_url.trim().trim_end_matches('/');
+        let parsed = url::Url::parse(base).map_err(|e| format!("Geçersiz Firecrawl adresi: {}", e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err("Firecrawl adresi http(s) olmalı".to_string());
+        }
+        if !matches!(self.api_version.as_str(), "v0" | "v1") {
+            return Err(format!("Desteklenmeyen Firecrawl API sürümü: {}", self.api_version));
+        }
+        let options = serde_json::to_string(&self.options).map_err(|e| format!("Seçenekler serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_BASE_URL, base)?;
+        store.set_setting(KEY_API_VERSION, &self.api_version)?;
+        // Maske geri geldiyse anahtar değişmemiştir; kayıtlı değer korunur
+        let key = self.api_key.as_deref().unwrap_or("").trim();
+        if key != crate::SECRET_MASK {
+            store.set_setting(KEY_API_KEY, key)?;
+        }
+        store.set_setting(KEY_OPTIONS, &options)
+    }
+
+    // Webview'a giden kopya: anahtar yalnızca kayıtlı olduğunu gösteren maskeyle gider
+    pub fn masked(mut self) -> Self {
+        if self.api_key.is_some() {
+            self.api_key = Some(crate::SECRET_MASK.to_string());
+        }
+        self
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}/{}", self.base_url.trim_end_matches('/'), self.api_version, path)
+    }
+
+    fn is_v1(&self) -> bool { self.api_version == "v1" }
+
+    fn same_origin(&self, other: &str) -> bool {
+        match (url::Url::parse(self.base_url.trim()), url::Url::parse(other)) {
+            (Ok(a), Ok(b)) => a.origin() == b.origin(),
+            _ => false,
+        }
+    }
+
+    fn is_default_host(&self) -> bool {
+        self.base_url.trim_end_matches('/') == DEFAULT_BASE_URL
+    }
+
+    // Kullanılacak anahtar; gömülü SaaS anahtarı yalnızca SaaS adresine gönderilir
+    fn bearer(&self) -> Option<String> {
+        match &self.api_key {
+            Some(k) => Some(k.clone()),
+            None if self.is_default_host() => crate::read_api_key().ok(),
+            None => None,
+        }
+    }
+
+    // Kendi sunucusunda anahtar zorunlu değil; SaaS için anahtar yoksa Firecrawl atlanır
+    pub fn is_usable(&self) -> bool {
+        !self.is_default_host() || self.bearer().is_some()
+    }
+
+    fn post(&self, client: &reqwest::Client, path: &str) -> reqwest::RequestBuilder {
+        let mut req = client.post(self.endpoint(path)).header("Content-Type", "application/json");
+        if let Some(key) = self.bearer() { req = req.header("Authorization", format!("Bearer {}", key)); }
+        req
+    }
+
+    fn get(&self, client: &reqwest::Client, path: &str) -> reqwest::RequestBuilder {
+        let mut req = client.get(self.endpoint(path));
+        if let Some(key) = self.bearer() { req = req.header("Authorization", format!("Bearer {}", key)); }
+        req
+    }
+
+    // v1: seçenekler gövdenin üst seviyesinde; markdown her zaman istenir
+    fn v1_scrape_options(&self) -> serde_json::Value {
+        let o = &self.options;
+        let mut formats = o.formats.clone();
+        if !formats.iter().any(|f| f == "markdown") { formats.insert(0, "markdown".to_string()); }
+        let mut body = serde_json::json!({ "formats": formats, "onlyMainContent": o.only_main_content });
+        if !o.include_tags.is_empty() { body["includeTags"] = serde_json::json!(o.include_tags); }
+        if !o.exclude_tags.is_empty() { body["excludeTags"] = serde_json::json!(o.exclude_tags); }
+        if let Some(w) = o.wait_for { body["waitFor"] = serde_json::json!(w); }
+        if let Some(t) = o.timeout { body["timeout"] = serde_json::json!(t); }
+        body
+    }
+
+    // v0: aynı seçenekler pageOptions altında ve farklı adlarla
+    fn v0_page_options(&self) -> serde_json::Value {
+        let o = &self.options;
+        let mut page = serde_json::json!({ "onlyMainContent": o.only_main_content });
+        if !o.include_tags.is_empty() { page["onlyIncludeTags"] = serde_json::json!(o.include_tags); }
+        if !o.exclude_tags.is_empty() { page["removeTags"] = serde_json::json!(o.exclude_tags); }
+        if let Some(w) = o.wait_for { page["waitFor"] = serde_json::json!(w); }
+        page
+    }
+}
+
+pub async fn scrape(url: &str, client: &reqwest::Client, config: &FirecrawlConfig) -> Result<String, String> {
+    info!("Önce /scrape deneniyor ({}): {}", config.api_version, url);
+    let request_body = if config.is_v1() {
+        let mut body = config.v1_scrape_options();
+        body["url"] = serde_json::json!(url);
+        body
+    } else {
+        let mut body = serde_json::json!({ "url": url, "pageOptions": config.v0_page_options() });
+        if let Some(t) = config.options.timeout { body["timeout"] = serde_json::json!(t); }
+        body
+    };
+    let response = config
+        .post(client, "scrape")
+        .json(&request_body)
+        .send()
+        .await
+        .map_err(|e| format!("/scrape isteği başarısız: {}", e))?;
+
+    let status = response.status();
+    let text = response.text().await.map_err(|e| format!("/scrape yanıtı okunamadı: {}", e))?;
+    info!("/scrape durumu: {}, yanıt (ilk 200): {}", status, crate::readability::truncate_utf8(&text, 200));
+
+    if status.is_success() {
+        let json_res: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("/scrape JSON parse hatası: {}", e))?;
+        if let Some(markdown) = json_res.get("data").and_then(|d| document_markdown(d, url)) {
+            return Ok(markdown);
+        }
+        if let Some(err) = json_res.get("error").and_then(|e| e.as_str()) {
+            return Err(format!("/scrape hatası: {}", err));
+        }
+    }
+
+    // /scrape başarısız olursa, hatayı /crawl'a geçmek için sinyal olarak kullan.
+    Err(format!("/scrape başarısız oldu veya içerik boş. Durum: {}", status))
+}
+
+pub async fn crawl(url: &str, client: &reqwest::Client, config: &FirecrawlConfig) -> Result<String, String> {
+    info!("/scrape başarısız oldu, /crawl deneniyor ({}): {}", config.api_version, url);
+
+    // Yalnızca verilen sayfa
+    let crawl_request_body = if config.is_v1() {
+        serde_json::json!({ "url": url, "limit": 1, "scrapeOptions": config.v1_scrape_options() })
+    } else {
+        serde_json::json!({ "url": url, "crawlerOptions": { "limit": 1 }, "pageOptions": config.v0_page_options() })
+    };
+    let data = run_crawl(client, config, &crawl_request_body).await?;
+    document_markdown(&data, url).ok_or_else(|| "Crawl tamamlandı ancak içerik alınamadı.".to_string())
+}
+
+// Site taraması sonucu tek sayfa
+pub struct CrawledDocument {
+    pub url: String,
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+// Site tarama modu için çok sayfalı crawl: sayfa ve derinlik sınırı Firecrawl'a iletilir.
+pub async fn crawl_site(url: &str, client: &reqwest::Client, config: &FirecrawlConfig, limit: usize, max_depth: u32) -> Result<Vec<CrawledDocument>, String> {
+    info!("Firecrawl site taraması ({}): {} (limit {}, derinlik {})", config.api_version, url, limit, max_depth);
+    let body = if config.is_v1() {
+        serde_json::json!({
+            "url": url,
+            "limit": limit,
+            "maxDepth": max_depth,
+            "allowExternalLinks": false,
+            "scrapeOptions": config.v1_scrape_options()
+        })
+    } else {
+        serde_json::json!({
+            "url": url,
+            "crawlerOptions": { "limit": limit, "maxDepth": max_depth },
+            "pageOptions": config.v0_page_options()
+        })
+    };
+    let data = run_crawl(client, config, &body).await?;
+    let docs: Vec<CrawledDocument> = data
+        .as_array()
+        .map(|arr| arr.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|doc| {
+            let meta = doc.get("metadata");
+            let doc_url = meta
+                .and_then(|m| m.get("sourceURL").or_else(|| m.get("url")))
+                .and_then(|v| v.as_str())
+                .unwrap_or(url)
+                .to_string();
+            let title = meta.and_then(|m| m.get("title")).and_then(|v| v.as_str()).map(|s| s.to_string());
+            let markdown = document_markdown(doc, &doc_url)?;
+            Some(CrawledDocument { url: doc_url, title, markdown })
+        })
+        .collect();
+    if docs.is_empty() {
+        return Err("Firecrawl site taraması içerik döndürmedi".to_string());
+    }
+    Ok(docs)
+}
+
+// Crawl işini başlatır ve tamamlanana kadar durumunu yoklar; belge listesini (data) döner.
+async fn run_crawl(client: &reqwest::Client, config: &FirecrawlConfig, body: &serde_json::Value) -> Result<serde_json::Value, String> {
+    // Adım 1: Crawl işini başlat
+    let crawl_response = config
+        .post(client, "crawl")
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Firecrawl /crawl isteği başarısız: {}", e))?;
+
+    if !crawl_response.status().is_success() {
+        return Err(format!("Firecrawl /crawl başlatma hatası: {}", crawl_response.status()));
+    }
+
+    let crawl_response_json: serde_json::Value = crawl_response.json().await.map_err(|e| format!("Firecrawl /crawl yanıtı JSON'a çevrilemedi: {}", e))?;
+    // v0: jobId, v1: id
+    let job_id = crawl_response_json["id"]
+        .as_str()
+        .or_else(|| crawl_response_json["jobId"].as_str())
+        .ok_or("Crawl yanıtında iş kimliği (id/jobId) bulunamadı")?
+        .to_string();
+    info!("Crawl işi başlatıldı, id: {}", job_id);
+
+    // Adım 2: Durumu kontrol et
+    let status_path = if config.is_v1() { format!("crawl/{}", job_id) } else { format!("crawl/status/{}", job_id) };
+    let max_retries = 20;
+    for i in 0..max_retries {
+        info!("Crawl durumu kontrol ediliyor... Deneme {}", i + 1);
+        let status_response = config
+            .get(client, &status_path)
+            .send()
+            .await
+            .map_err(|e| format!("Firecrawl durum kontrolü başarısız: {}", e))?;
+
+        if !status_response.status().is_success() {
+            // Durum kontrolü geçici olarak başarısız olabilir, beklemeye devam et
+            warn!("Durum kontrolü geçici hata verdi: {}. Tekrar denenecek.", status_response.status());
+            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+            continue;
+        }
+
+        let status_json: serde_json::Value = status_response.json().await.map_err(|e| format!("Firecrawl durum yanıtı JSON'a çevrilemedi: {}", e))?;
+        if let Some(status) = status_json["status"].as_str() {
+            match status {
+                "completed" => {
+                    info!("Crawl tamamlandı!");
+                    return Ok(collect_pages(client, config, &status_json).await);
+                },
+                // v0: active/crawling/paused, v1: scraping
+                "crawling" | "active" | "scraping" | "paused" => {
+                    info!("Crawl devam ediyor... ({})", status);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                },
+                "failed" | "cancelled" => return Err(format!("Firecrawl işi başarısız oldu: {}", status_json["error"].as_str().unwrap_or(status))),
+                _ => return Err(format!("Bilinmeyen crawl durumu: {}", status)),
+            }
+        } else {
+            return Err("Durum yanıtında 'status' alanı bulunamadı.".to_string());
+        }
+    }
+
+    Err("Firecrawl işi zaman aşımına uğradı.".to_string())
+}
+
+// v1 büyük sonuçları sayfalara böler ve `next` ile devam adresini verir; hepsini tek diziye toplar.
+// v0'da `data` zaten dizi (eski sürümlerde tek nesne) olarak gelir.
+async fn collect_pages(client: &reqwest::Client, config: &FirecrawlConfig, first: &serde_json::Value) -> serde_json::Value {
+    let mut docs: Vec<serde_json::Value> = Vec::new();
+    let mut push = |data: Option<&serde_json::Value>| match data {
+        Some(serde_json::Value::Array(arr)) => docs.extend(arr.iter().cloned()),
+        Some(obj @ serde_json::Value::Object(_)) => docs.push(obj.clone()),
+        _ => {}
+    };
+    push(first.get("data"));
+    let mut next = first.get("next").and_then(|n| n.as_str()).map(|s| s.to_string());
+    let mut pages = 0;
+    while let Some(next_url) = next.take() {
+        pages += 1;
+        if pages > 10 { break; }
+        // Anahtar yalnızca Firecrawl sunucusuna gider; başka kökene işaret eden devam adresi izlenmez
+        if !config.same_origin(&next_url) {
+            warn!("Firecrawl devam adresi farklı bir kökende, sayfalama durduruldu: {}", next_url);
+            break;
+        }
+        let mut req = client.get(&next_url);
+        if let Some(key) = config.bearer() { req = req.header("Authorization", format!("Bearer {}", key)); }
+        let json: serde_json::Value = match req.send().await {
+            Ok(r) if r.status().is_success() => match r.json().await { Ok(j) => j, Err(_) => break },
+            _ => break,
+        };
+        push(json.get("data"));
+        next = json.get("next").and_then(|n| n.as_str()).map(|s| s.to_string());
+    }
+    serde_json::Value::Array(docs)
+}
+
+// `data` tek belge (scrape) ya da belge dizisi (crawl) olabilir. Dizide istenen URL'ye
+// ait belge tercih edilir. Markdown yoksa html/rawHtml (v1) ya da content (v0) kullanılır.
+fn document_markdown(data: &serde_json::Value, url: &str) -> Option<String> {
+    let doc = match data.as_array() {
+        Some(docs) => docs
+            .iter()
+            .find(|d| {
+                let meta = d.get("metadata");
+                let src = meta.and_then(|m| m.get("sourceURL").or_else(|| m.get("url"))).and_then(|v| v.as_str());
+                src.map(|s| s.trim_end_matches('/') == url.trim_end_matches('/')).unwrap_or(false)
+            })
+            .or_else(|| docs.first())?,
+        None => data,
+    };
+    let text = |key: &str| doc.get(key).and_then(|v| v.as_str()).filter(|s| !s.trim().is_empty());
+    if let Some(md) = text("markdown") { return Some(md.to_string()); }
+    if let Some(html) = text("html").or_else(|| text("rawHtml")) {
+        let md = markdown::html_to_markdown(html, Some(url));
+        if !md.trim().is_empty() { return Some(md); }
+    }
+    text("content").map(|c| c.to_string())
+}
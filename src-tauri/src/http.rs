@@ -0,0 +1,194 @@
+// Paylaşılan HTTP istemcisi.
+// Tüm istekler tek bir reqwest::Client üzerinden gider; bağlantı havuzu ve TLS oturumları yeniden
+// kullanılır, gzip/brotli/deflate çözülür, sunucu destekliyorsa HTTP/2 seçilir. Zaman aşımları
+// amaca göre profillerden gelir: sayfa çekme ve meta veri isteklerinde toplam süre, LLM akışlarında
+// ilk yanıt ve iki parça arasındaki sessizlik süresi sınırlanır. Ayarlar app_settings'te saklanır.
+
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+use crate::{polite, proxy, ChatStore};
+
+const KEY_HTTP: &str = "http_timeouts";
+const MAX_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    // Sayfa, PDF, akış, görsel indirme
+    Scraper,
+    // robots.txt, oEmbed, model listeleri gibi küçük yanıtlar
+    Metadata,
+    // Ollama / OpenRouter akışları: toplam süre yok, ilk yanıt ve sessizlik sınırlı
+    Llm,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HttpConfig {
+    pub connect_timeout_secs: u64,
+    pub scraper_timeout_secs: u64,
+    pub metadata_timeout_secs: u64,
+    // Model yüklenirken ilk yanıt gecikebilir
+    pub llm_first_byte_secs: u64,
+    // Akış sırasında iki parça arasındaki en uzun sessizlik (okuma zaman aşımı)
+    pub read_timeout_secs: u64,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self { connect_timeout_secs: 10, scraper_timeout_secs: 20, metadata_timeout_secs: 10, llm_first_byte_secs: 180, read_timeout_secs: 60 }
+    }
+}
+
+impl HttpConfig {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_HTTP)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        let fields = [
+            ("Bağlantı", self.connect_timeout_secs),
+            ("Sayfa çekme", self.scraper_timeout_secs),
+            ("Meta veri", self.metadata_timeout_secs),
+            ("Model ilk yanıt", self.llm_first_byte_secs),
+            ("Okuma", self.read_timeout_secs),
+        ];
+        for (name, secs) in fields {
+            if secs == 0 || secs > MAX_TIMEOUT_SECS {
+                return Err(format!("{} zaman aşımı 1-{} sn olmalı", name, MAX_TIMEOUT_SECS));
+            }
+        }
+        let raw = serde_json::to_string(self).map_err(|e| format!("HTTP ayarları serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_HTTP, &raw)
+    }
+
+    fn total(&self, profile: Profile) -> Option<Duration> {
+        match profile {
+            Profile::Scraper => Some(Duration::from_secs(self.scraper_timeout_secs)),
+            Profile::Metadata => Some(Duration::from_secs(self.metadata_timeout_secs)),
+            Profile::Llm => None,
+        }
+    }
+
+    pub fn first_byte(&self) -> Duration {
+        Duration::from_secs(self.llm_first_byte_secs)
+    }
+
+    pub fn idle(&self) -> Duration {
+        Duration::from_secs(self.read_timeout_secs)
+    }
+}
+
+struct Inner {
+    client: RwLock<reqwest::Client>,
+    config: RwLock<HttpConfig>,
+}
+
+// Tauri state'te tutulur; kopyaları aynı istemciyi (ve havuzu) paylaşır
+#[derive(Clone)]
+pub struct Http {
+    inner: Arc<Inner>,
+}
+
+impl Http {
+    pub fn new(config: HttpConfig) -> Result<Self, String> {
+        let client = build(&config)?;
+        Ok(Self { inner: Arc::new(Inner { client: RwLock::new(client), config: RwLock::new(config) }) })
+    }
+
+    pub fn client(&self) -> reqwest::Client {
+        self.inner.client.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn config(&self) -> HttpConfig {
+        self.inner.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    pub fn configure(&self, config: HttpConfig) -> Result<(), String> {
+        let client = build(&config)?;
+        if let Ok(mut c) = self.inner.client.write() { *c = client; }
+        if let Ok(mut c) = self.inner.config.write() { *c = config; }
+        Ok(())
+    }
+
+    // Vekil ayarı değişince havuz yeni vekille baştan kurulur
+    pub fn rebuild(&self) -> Result<(), String> {
+        self.configure(self.config())
+    }
+
+    pub fn request(&self, method: reqwest::Method, url: &str, profile: Profile) -> reqwest::RequestBuilder {
+        let req = self.client().request(method, url);
+        match self.config().total(profile) {
+            Some(t) => req.timeout(t),
+            None => req,
+        }
+    }
+
+    pub fn get(&self, url: &str, profile: Profile) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::GET, url, profile)
+    }
+
+    pub fn post(&self, url: &str, profile: Profile) -> reqwest::RequestBuilder {
+        self.request(reqwest::Method::POST, url, profile)
+    }
+
+    // LLM isteği: yanıt başlıkları ilk yanıt süresi içinde gelmezse hata
+    pub async fn send_streaming(&self, req: reqwest::RequestBuilder) -> Result<reqwest::Response, String> {
+        let limit = self.config().first_byte();
+        match tokio::time::timeout(limit, req.send()).await {
+            Ok(r) => r.map_err(|e| e.to_string()),
+            Err(_) => Err(format!("model {} sn içinde yanıt vermedi", limit.as_secs())),
+        }
+    }
+
+    // Akıştan sıradaki parça; okuma zaman aşımı kadar sessiz kalırsa hata
+    pub async fn next_chunk<S, T>(&self, stream: &mut S) -> Result<Option<T>, String>
+    where
+        S: Stream<Item = reqwest::Result<T>> + Unpin,
+    {
+        let idle = self.config().idle();
+        match tokio::time::timeout(idle, stream.next()).await {
+            Ok(Some(chunk)) => chunk.map(Some).map_err(|e| format!("Stream'den chunk okunamadı: {}", e)),
+            Ok(None) => Ok(None),
+            Err(_) => Err(format!("akış {} sn boyunca veri göndermedi", idle.as_secs())),
+        }
+    }
+}
+
+fn build(config: &HttpConfig) -> Result<reqwest::Client, String> {
+    proxy::client_builder()?
+        .user_agent(polite::default_user_agent())
+        .connect_timeout(Duration::from_secs(config.connect_timeout_secs))
+        .gzip(true)
+        .brotli(true)
+        .deflate(true)
+        .http2_adaptive_window(true)
+        .pool_idle_timeout(Duration::from_secs(90))
+        .pool_max_idle_per_host(8)
+        .tcp_keepalive(Duration::from_secs(60))
+        .build()
+        .map_err(|e| format!("HTTP client oluşturulamadı: {}", e))
+}
+
+// ---- Tauri state dışından (nezaket katmanı, çıkarıcılar) erişim ----
+
+static SHARED: OnceLock<Http> = OnceLock::new();
+
+// Açılışta Tauri state'e konan örnek burada da kaydedilir
+pub fn install(http: Http) {
+    let _ = SHARED.set(http);
+}
+
+pub fn shared() -> &'static Http {
+    SHARED.get_or_init(|| {
+        Http::new(HttpConfig::default()).unwrap_or_else(|_| Http {
+            inner: Arc::new(Inner { client: RwLock::new(reqwest::Client::new()), config: RwLock::new(HttpConfig::default()) }),
+        })
+    })
+}
@@ -0,0 +1,204 @@
+// Görsel destekli (multimodal) modeller için sayfa görselleri.
+// og:image / twitter:image ve ana içerikteki <img> öğeleri, sayfa çıkarımı sırasında zaten alınmış
+// HTML'den aday olarak toplanır ve sayfayla birlikte saklanır (cache dahil); ikon, logo,
+// izleme pikseli gibi küçük görseller elenir. Görseller nezaket katmanı üzerinden indirilip
+// boyut sınırları içinde base64'e çevrilir (Ollama `images`, OpenRouter `image_url`).
+
+use base64::Engine;
+use futures::StreamExt;
+use log::{info, warn};
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::http::Profile;
+use crate::{polite, readability};
+
+// Soru başına en fazla görsel sayısı ve boyut sınırları
+const MAX_IMAGES: usize = 4;
+const MAX_IMAGE_BYTES: usize = 2 * 1024 * 1024;
+const MAX_TOTAL_BYTES: usize = 6 * 1024 * 1024;
+// width/height öznitelikleri bundan küçükse ikon/piksel kabul edilir
+const MIN_DIMENSION: u32 = 100;
+// Adında bunlar geçen görseller içerik değildir
+const SKIP_HINTS: &[&str] = &["logo", "icon", "sprite", "avatar", "pixel", "spacer", "blank", "badge", "emoji"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageImage {
+    pub url: String,
+    pub alt: Option<String>,
+    pub mime: String,
+    pub size: usize,
+    #[serde(skip)]
+    pub base64: String,
+}
+
+// Henüz indirilmemiş aday görsel (ScrapedPage / page_cache kaydında tutulur)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Candidate {
+    pub url: String,
+    pub alt: Option<String>,
+}
+
+impl PageImage {
+    // OpenRouter/OpenAI image_url parçası için data URI
+    pub fn data_uri(&self) -> String {
+        format!("data:{};base64,{}", self.mime, self.base64)
+    }
+}
+
+// Aday görsel adresleri (önem sırasıyla): önce paylaşım görseli, sonra ana içerikteki görseller
+pub fn candidates(html: &str, page_url: &str) -> Vec<Candidate> {
+    let Ok(base) = url::Url::parse(page_url) else { return Vec::new() };
+    let doc = Html::parse_document(html);
+    let mut out: Vec<(url::Url, Option<String>)> = Vec::new();
+
+    if let Ok(sel) = Selector::parse("meta[property='og:image'], meta[property='og:image:url'], meta[name='twitter:image']") {
+        for meta in doc.select(&sel) {
+            if let Some(u) = meta.value().attr("content").and_then(|c| base.join(c.trim()).ok()) {
+                push_candidate(&mut out, u, None);
+            }
+        }
+    }
+
+    let root = readability::find_main_content(&doc).unwrap_or_else(|| doc.root_element());
+    if let Ok(sel) = Selector::parse("img") {
+        for img in root.select(&sel) {
+            if readability::has_unlikely_ancestor(img) || too_small(img) { continue; }
+            let Some(src) = image_source(img) else { continue };
+            let Ok(u) = base.join(&src) else { continue };
+            let alt = img.value().attr("alt").map(readability::collapse_ws).filter(|a| !a.is_empty());
+            push_candidate(&mut out, u, alt);
+        }
+    }
+    finish(out)
+}
+
+// HTML'i elimizde olmayan kaynaklar (Firecrawl vb.) için Markdown'daki ![alt](adres) görselleri
+pub fn from_markdown(markdown: &str, page_url: &str) -> Vec<Candidate> {
+    let Ok(base) = url::Url::parse(page_url) else { return Vec::new() };
+    let mut out: Vec<(url::Url, Option<String>)> = Vec::new();
+    let mut rest = markdown;
+    while let Some(idx) = rest.find("![") {
+        rest = &rest[idx + 2..];
+        let Some(close) = rest.find("](") else { break };
+        let alt = readability::collapse_ws(&rest[..close]);
+        let tail = &rest[close + 2..];
+        let end = tail.find(|c: char| c == ')' || c.is_whitespace()).unwrap_or(tail.len());
+        if let Ok(u) = base.join(&tail[..end]) {
+            push_candidate(&mut out, u, Some(alt).filter(|a| !a.is_empty()));
+        }
+        rest = &tail[end..];
+    }
+    finish(out)
+}
+
+fn finish(found: Vec<(url::Url, Option<String>)>) -> Vec<Candidate> {
+    found.into_iter().map(|(u, alt)| Candidate { url: u.to_string(), alt }).collect()
+}
+
+fn push_candidate(out: &mut Vec<(url::Url, Option<String>)>, u: url::Url, alt: Option<String>) {
+    if !matches!(u.scheme(), "http" | "https") { return; }
+    if out.iter().any(|(seen, _)| seen == &u) { return; }
+    let path = u.path().to_lowercase();
+    if path.ends_with(".svg") || path.ends_with(".ico") { return; }
+    if SKIP_HINTS.iter().any(|h| path.contains(h)) { return; }
+    out.push((u, alt));
+}
+
+fn too_small(img: ElementRef<'_>) -> bool {
+    let dim = |name: &str| img.value().attr(name).and_then(|v| v.trim().trim_end_matches("px").parse::<u32>().ok());
+    matches!(dim("width"), Some(w) if w < MIN_DIMENSION) || matches!(dim("height"), Some(h) if h < MIN_DIMENSION)
+}
+
+// srcset varsa en geniş aday, yoksa src / tembel yükleme öznitelikleri
+fn image_source(img: ElementRef<'_>) -> Option<String> {
+    let v = img.value();
+    if let Some(srcset) = v.attr("srcset").or_else(|| v.attr("data-srcset")) {
+        let best = srcset
+            .split(',')
+            .filter_map(|part| {
+                let mut it = part.split_whitespace();
+                let url = it.next()?;
+                let width = it.next().and_then(|d| d.trim_end_matches(['w', 'x']).parse::<f32>().ok()).unwrap_or(1.0);
+                Some((url.to_string(), width))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((url, _)) = best { return Some(url); }
+    }
+    ["src", "data-src", "data-original", "data-lazy-src"]
+        .iter()
+        .filter_map(|a| v.attr(a))
+        .map(str::trim)
+        .find(|s| !s.is_empty() && !s.starts_with("data:"))
+        .map(str::to_string)
+}
+
+// Adayları sırayla indirir; desteklenmeyen biçim ya da sınırı aşan görseller atlanır.
+pub async fn collect(candidates: &[Candidate]) -> Vec<PageImage> {
+    let mut images = Vec::new();
+    let mut total = 0usize;
+    // Her indirme host hız sınırına tabi; çok sayıda aday soruyu geciktirmesin
+    for candidate in candidates.iter().take(MAX_IMAGES * 2) {
+        if images.len() >= MAX_IMAGES { break; }
+        match fetch_image(&candidate.url).await {
+            Ok((mime, bytes)) => {
+                if total + bytes.len() > MAX_TOTAL_BYTES { continue; }
+                total += bytes.len();
+                images.push(PageImage {
+                    url: candidate.url.clone(),
+                    alt: candidate.alt.clone(),
+                    mime,
+                    size: bytes.len(),
+                    base64: base64::engine::general_purpose::STANDARD.encode(&bytes),
+                });
+            }
+            Err(e) => warn!("Görsel alınamadı: {} ({})", candidate.url, e),
+        }
+    }
+    info!("{} görsel modele eklenecek ({} bayt)", images.len(), total);
+    images
+}
+
+async fn fetch_image(url: &str) -> Result<(String, Vec<u8>), String> {
+    let resp = polite::get(url, Profile::Scraper).await?;
+    if !resp.status().is_success() { return Err(format!("HTTP {}", resp.status())); }
+    if resp.content_length().map(|n| n as usize > MAX_IMAGE_BYTES).unwrap_or(false) {
+        return Err("görsel boyut sınırını aşıyor".to_string());
+    }
+    let header = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.split(';').next().unwrap_or("").trim().to_lowercase());
+    // Content-Length olmayabilir ya da yanlış olabilir: gövde parça parça okunur, sınır aşılınca kesilir
+    let mut bytes = Vec::new();
+    let mut stream = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("yanıt okunamadı: {}", e))?;
+        if bytes.len() + chunk.len() > MAX_IMAGE_BYTES { return Err("görsel boyut sınırını aşıyor".to_string()); }
+        bytes.extend_from_slice(&chunk);
+    }
+    // Content-Type güvenilmez olabildiği için imzaya da bakılır
+    let mime = sniff_mime(&bytes).or(header.filter(|h| h.starts_with("image/"))).unwrap_or_default();
+    if !matches!(mime.as_str(), "image/jpeg" | "image/png" | "image/webp") {
+        return Err(format!("desteklenmeyen biçim: {}", if mime.is_empty() { "bilinmiyor" } else { &mime }));
+    }
+    Ok((mime, bytes))
+}
+
+fn sniff_mime(bytes: &[u8]) -> Option<String> {
+    let mime = if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.len() > 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else {
+        return None;
+    };
+    Some(mime.to_string())
+}
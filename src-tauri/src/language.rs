@@ -0,0 +1,188 @@
+// Sayfa ve soru dili tespiti, cevap dili ayarı.
+// Dil, çıkarılmış metindeki yazı sistemi (Kiril, Arap, Hangıl, Kana, Han, Yunan) ve sık geçen
+// işlev sözcükleri sayılarak tahmin edilir. Cevap dili ayarı: "auto" (sorunun dili), "fixed" (seçilen
+// dil) ya da "page" (sayfanın dili). Modele giden dil talimatı bu ayardan üretilir.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ChatStore;
+
+const KEY_ANSWER_LANGUAGE: &str = "answer_language";
+// Uzun sayfalarda yalnızca baştaki sözcüklere bakılır
+const MAX_WORDS: usize = 3000;
+// Sözcük sayımıyla karar vermek için gereken en az isabet
+const MIN_HITS: usize = 2;
+
+// (kod, Türkçe ad, İngilizce ad)
+pub const LANGUAGES: &[(&str, &str, &str)] = &[
+    ("tr", "Türkçe", "Turkish"),
+    ("en", "İngilizce", "English"),
+    ("de", "Almanca", "German"),
+    ("fr", "Fransızca", "French"),
+    ("es", "İspanyolca", "Spanish"),
+    ("it", "İtalyanca", "Italian"),
+    ("nl", "Felemenkçe", "Dutch"),
+    ("pt", "Portekizce", "Portuguese"),
+    ("ru", "Rusça", "Russian"),
+    ("el", "Yunanca", "Greek"),
+    ("ar", "Arapça", "Arabic"),
+    ("zh", "Çince", "Chinese"),
+    ("ja", "Japonca", "Japanese"),
+    ("ko", "Korece", "Korean"),
+];
+
+// Dile özgü sık sözcükler. Birden fazla dilde geçen sözcükler ("de", "que", "die", "is", "e")
+// hiçbir listede yok: eşit isabet diller arasında beraberliğe ve sonuçsuz tespite yol açar.
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("tr", &["ve", "bir", "bu", "için", "ile", "çok", "daha", "gibi", "olan", "olarak", "ama", "değil", "mi", "mı", "mu", "mü", "nasıl", "neden", "nedir", "var", "yok", "her", "kadar", "sonra", "şey", "veya", "ise", "şu", "göre", "hangi", "nerede", "oldu", "olduğu"]),
+    ("en", &["the", "and", "of", "to", "that", "it", "for", "with", "was", "on", "are", "be", "this", "by", "or", "have", "from", "at", "which", "you", "but", "what", "how", "why", "can", "does", "an", "were", "will", "their", "there", "about"]),
+    ("de", &["der", "und", "ist", "nicht", "ein", "eine", "zu", "den", "von", "mit", "sich", "auf", "für", "im", "dem", "auch", "werden", "aus", "sie", "wie", "warum", "kann", "ich", "wir", "oder", "bei", "nach", "über", "wird", "wo", "welche", "diese", "diesem", "dieser", "worum", "gibt", "sind", "nur", "noch"]),
+    ("fr", &["le", "les", "et", "est", "une", "du", "qui", "dans", "pour", "pas", "sur", "au", "avec", "ce", "sont", "par", "plus", "nous", "vous", "comment", "pourquoi", "quoi", "cette", "aux", "où"]),
+    ("es", &["el", "los", "las", "y", "es", "se", "lo", "más", "pero", "su", "al", "qué", "cómo", "está", "son", "este", "esta", "dónde", "cuál", "también", "muy", "hay", "cuando"]),
+    ("it", &["di", "che", "per", "non", "sono", "della", "come", "anche", "nel", "ma", "gli", "questo", "perché", "cosa", "più", "è", "dove", "quale", "alla"]),
+    ("nl", &["het", "een", "en", "van", "dat", "op", "te", "niet", "zijn", "met", "voor", "ook", "maar", "als", "er", "wat", "hoe", "waarom", "bij", "naar", "om", "aan", "wordt", "dit", "waar"]),
+    ("pt", &["o", "os", "do", "da", "em", "um", "uma", "com", "não", "dos", "na", "no", "é", "são", "foi", "mas", "ao", "pelo", "isso", "onde", "também", "muito", "quando", "pela"]),
+    ("ru", &["и", "в", "не", "на", "что", "я", "с", "он", "как", "это", "по", "но", "из", "к", "у", "за", "от", "то", "для", "так", "же", "все", "был", "она", "они", "мы", "вы", "почему", "где"]),
+];
+
+// Kısa metinlerde (sorular) belirleyici harfler
+const LETTER_HINTS: &[(&str, &[char])] = &[
+    ("tr", &['ğ', 'ı', 'ş', 'İ']),
+    ("de", &['ß', 'ä']),
+    ("es", &['ñ', '¿', '¡']),
+    ("pt", &['ã', 'õ']),
+    ("fr", &['œ', 'è', 'ê']),
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Detected {
+    pub code: &'static str,
+    pub name: &'static str, // İngilizce ad
+    pub confidence: f32,    // 0-1; en yakın ikinci dile göre fark
+}
+
+pub fn detect(text: &str) -> Option<Detected> {
+    detect_script(text).or_else(|| detect_words(text))
+}
+
+// Latin dışı yazı sistemleri harflerin çoğunluğunu oluşturuyorsa dil doğrudan belirlenir
+fn detect_script(text: &str) -> Option<Detected> {
+    let (mut letters, mut cyrillic, mut arabic, mut greek, mut hangul, mut kana, mut han) = (0usize, 0, 0, 0, 0, 0, 0);
+    for c in text.chars().filter(|c| c.is_alphabetic()).take(MAX_WORDS * 5) {
+        letters += 1;
+        match c as u32 {
+            0x0400..=0x04FF => cyrillic += 1,
+            0x0600..=0x06FF | 0x0750..=0x077F => arabic += 1,
+            0x0370..=0x03FF => greek += 1,
+            0xAC00..=0xD7AF | 0x1100..=0x11FF => hangul += 1,
+            0x3040..=0x30FF => kana += 1,
+            0x4E00..=0x9FFF | 0x3400..=0x4DBF => han += 1,
+            _ => {}
+        }
+    }
+    if letters == 0 { return None; }
+    let share = |n: usize| n as f32 / letters as f32;
+    // Japonca metinlerde kana Han ile karışık geçer; az da olsa kana varsa Japonca sayılır
+    let code = if share(hangul) > 0.3 {
+        "ko"
+    } else if share(kana) > 0.05 && share(kana + han) > 0.3 {
+        "ja"
+    } else if share(han) > 0.3 {
+        "zh"
+    } else if share(arabic) > 0.3 {
+        "ar"
+    } else if share(greek) > 0.3 {
+        "el"
+    } else if share(cyrillic) > 0.3 {
+        "ru"
+    } else {
+        return None;
+    };
+    let dominant = [cyrillic, arabic, greek, hangul, kana + han].into_iter().max().unwrap_or(0);
+    Some(detected(code, share(dominant).min(1.0)))
+}
+
+fn detect_words(text: &str) -> Option<Detected> {
+    // "İ" Unicode'da "i̇" (i + birleşik nokta) olarak küçülür; Türkçe sözcükler ("İçin") eşleşsin diye önce i'ye çevrilir
+    let lower = text.replace('İ', "i").to_lowercase();
+    let mut scores: Vec<(&'static str, usize)> = STOPWORDS.iter().map(|(code, _)| (*code, 0)).collect();
+    for word in lower.split(|c: char| !c.is_alphabetic()).filter(|w| !w.is_empty()).take(MAX_WORDS) {
+        for (i, (_, words)) in STOPWORDS.iter().enumerate() {
+            if words.contains(&word) { scores[i].1 += 1; }
+        }
+    }
+    // Belirleyici harfler iki isabet sayılır; tek sözcüklük sorularda bile karar verilebilsin
+    for (code, chars) in LETTER_HINTS {
+        if text.chars().any(|c| chars.contains(&c)) {
+            if let Some(s) = scores.iter_mut().find(|(c, _)| c == code) { s.1 += 2; }
+        }
+    }
+    scores.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    let (code, best) = scores[0];
+    let second = scores.get(1).map(|s| s.1).unwrap_or(0);
+    if best < MIN_HITS || best == second { return None; }
+    Some(detected(code, (best - second) as f32 / best as f32))
+}
+
+fn detected(code: &'static str, confidence: f32) -> Detected {
+    let name = LANGUAGES.iter().find(|(c, ..)| *c == code).map(|(_, _, en)| *en).unwrap_or(code);
+    Detected { code, name, confidence: (confidence * 100.0).round() / 100.0 }
+}
+
+fn names(code: &str) -> Option<(&'static str, &'static str)> {
+    LANGUAGES.iter().find(|(c, ..)| *c == code).map(|(_, tr, en)| (*tr, *en))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AnswerLanguage {
+    pub mode: String,  // auto | fixed | page
+    pub fixed: String, // mode "fixed" iken dil kodu
+}
+
+impl Default for AnswerLanguage {
+    fn default() -> Self {
+        Self { mode: "auto".to_string(), fixed: "tr".to_string() }
+    }
+}
+
+impl AnswerLanguage {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_ANSWER_LANGUAGE)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        if !matches!(self.mode.as_str(), "auto" | "fixed" | "page") {
+            return Err(format!("Geçersiz cevap dili modu: {} (auto, fixed ya da page)", self.mode));
+        }
+        if names(&self.fixed).is_none() {
+            return Err(format!("Desteklenmeyen cevap dili: {}", self.fixed));
+        }
+        let raw = serde_json::to_string(self).map_err(|e| format!("Cevap dili ayarı serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_ANSWER_LANGUAGE, &raw)
+    }
+
+    // Cevabın yazılacağı dil kodu; belirlenemezse None (model sorunun dilinde cevaplar)
+    pub fn target(&self, question: &str, page: Option<&Detected>) -> Option<&'static str> {
+        let from_question = || detect(question).map(|d| d.code);
+        match self.mode.as_str() {
+            "fixed" => LANGUAGES.iter().find(|(c, ..)| *c == self.fixed).map(|(c, ..)| *c),
+            "page" => page.map(|d| d.code).or_else(from_question),
+            _ => from_question(),
+        }
+    }
+}
+
+// Kullanıcı mesajının sonuna eklenen talimat. İngilizce tekrar, Türkçe talimatları izlemekte
+// zorlanan modellerin de dili doğru seçmesi için.
+pub fn directive(target: Option<&str>) -> String {
+    match target.and_then(names) {
+        Some((tr, en)) => format!("Cevabı {} olarak, kısa ve net üret. (Answer in {}.)", tr, en),
+        None => "Cevabı sorunun sorulduğu dilde, kısa ve net üret. (Answer in the language of the question.)".to_string(),
+    }
+}
@@ -15,12 +15,54 @@ use redis::RedisError;
 use redis::cmd;
 use sha2::{Sha256, Digest};
 
+mod charset;
+mod cleanup;
+mod cookies;
+mod crawl;
+mod extractors;
+mod feeds;
+mod firecrawl;
+mod http;
+mod images;
+mod language;
+mod links;
+mod markdown;
+mod metadata;
+mod page_cache;
+mod pdf;
+mod polite;
+mod proxy;
+mod readability;
+mod strategy;
+mod structured;
+mod tables;
+
+// Webview'a geri gönderilen gizli ayarların (API anahtarı, vekil parolası) yerine geçen değer.
+// Setter bu değeri alırsa kayıtlı gizli değer değiştirilmez.
+const SECRET_MASK: &str = "••••••••";
+
 #[derive(Debug, Default)]
 pub struct AppState {
-    page_cache: Mutex<HashMap<String, CachedPage>>, // URL -> CachedPage
+    page_cache: Mutex<page_cache::PageCache>,      // URL -> sayfa (LRU, bayt bütçeli)
     tab_ids: Mutex<HashSet<String>>,               // Active webview ids
     current_urls: Mutex<HashMap<String, String>>,  // tab_id -> current url
     last_active_tab: Mutex<Option<String>>,        // last focused/used tab id
+    pending_dom: Mutex<HashMap<String, PendingDom>>, // request id -> canlı DOM bekleyen istek
+    site_corpora: Mutex<HashMap<String, crawl::SiteCorpus>>, // site kökeni -> tarama derlemi
+}
+
+// Canlı DOM isteği: yalnızca istenen sekmenin webview'ı yanıtlayabilir
+#[derive(Debug)]
+pub struct PendingDom {
+    tab_id: String,
+    tx: tokio::sync::oneshot::Sender<LiveDomSnapshot>,
+}
+
+// Sekmedeki render edilmiş belgenin anlık görüntüsü (submit_live_dom ile gelir)
+#[derive(Debug)]
+pub struct LiveDomSnapshot {
+    url: String,
+    html: String,
 }
 
 #[derive(Debug)]
@@ -62,6 +104,10 @@ impl ChatStore {
              CREATE INDEX IF NOT EXISTS idx_popular_sort ON popular_site(sort_order);
              "
         ).map_err(|e| format!("DB tablo oluşturma hatası: {}", e))?;
+        conn.execute_batch(page_cache::CREATE_TABLE).map_err(|e| format!("DB tablo oluşturma hatası: {}", e))?;
+        for migration in page_cache::MIGRATIONS {
+            let _ = conn.execute(migration, []);
+        }
         // Varsayılan popüler siteleri tek seferlik ekle
         if let Ok(mut stmt) = conn.prepare("SELECT COUNT(*) FROM popular_site") {
             if let Ok(mut rows) = stmt.query([]) {
@@ -167,6 +213,13 @@ impl ChatStore {
         Ok(())
     }
 
+    pub fn delete_setting(&self, key: &str) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.execute("DELETE FROM app_settings WHERE key = ?1", params![key])
+            .map_err(|e| format!("settings delete hatası: {}", e))?;
+        Ok(())
+    }
+
     pub fn clear_for_url(&self, url: &str) -> Result<(), String> {
         let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
         // İlgili session id'lerini bul
@@ -315,11 +368,73 @@ impl RedisLogger {
     }
 }
 
+// Scrape sonucu: model bağlamına girecek içerik ve nereden/nasıl alındığı
 #[derive(Debug, Clone)]
-struct CachedPage {
+struct ScrapedPage {
     content: String,
     source: String,
-    fetched_at: Instant,
+    encoding: Option<charset::EncodingInfo>, // yalnızca HTML'i kendimiz çözdüğümüzde dolu
+    attempts: Vec<strategy::Attempt>,        // bu sonuca gelene kadar çalışan adımlar
+    links: Vec<links::Link>,                 // bağlam ekindeki numaralı bağlantılar
+    cookies: bool,                           // sekme çerezleriyle mi çekildi
+    cleanup: Option<cleanup::Report>,        // kalıp metin temizliği (önce/sonra boyutları)
+    metadata: Option<metadata::PageMetadata>, // yazar, tarihler, canonical, okuma süresi
+    validators: page_cache::Validators,      // ETag / Last-Modified (yalnızca kendi HTTP adımlarımızda)
+    images: Vec<images::Candidate>,          // görsel destekli modeller için aday görseller
+}
+
+impl ScrapedPage {
+    fn new(content: String, source: &str) -> Self {
+        Self { content, source: source.to_string(), encoding: None, attempts: Vec::new(), links: Vec::new(), cookies: false, cleanup: None, metadata: None, validators: page_cache::Validators::default(), images: Vec::new() }
+    }
+
+    fn with_validators(mut self, validators: page_cache::Validators) -> Self {
+        self.validators = validators;
+        self
+    }
+
+    fn with_images(mut self, images: Vec<images::Candidate>) -> Self {
+        self.images = images;
+        self
+    }
+
+    fn with_metadata(mut self, metadata: metadata::PageMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    // Çerez/bülten/paylaş metinlerini, tekrar eden satırları ve bağlantı listelerini ayıklar
+    fn cleaned(mut self) -> Self {
+        let (content, report) = cleanup::clean(&self.content);
+        info!(
+            "İçerik temizliği ({}): {} -> {} byte ({} blok, {} satır atıldı{})",
+            self.source, report.before, report.after, report.removed_blocks, report.removed_lines,
+            if report.reverted { ", geri alındı" } else { "" }
+        );
+        self.content = content;
+        self.cleanup = Some(report);
+        self
+    }
+
+    fn with_cookies(mut self, used: bool) -> Self {
+        self.cookies = used;
+        self
+    }
+
+    fn with_links(mut self, links: Vec<links::Link>) -> Self {
+        self.links = links;
+        self
+    }
+
+    fn with_encoding(mut self, encoding: charset::EncodingInfo) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    fn with_attempts(mut self, attempts: Vec<strategy::Attempt>) -> Self {
+        self.attempts = attempts;
+        self
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -518,17 +633,16 @@ fn read_openrouter_api_key() -> Result<String, String> {
 
 // Ollama modellerini getir
 #[tauri::command]
-async fn get_ollama_models(store: tauri::State<'_, ChatStore>) -> Result<Vec<OllamaModel>, String> {
+async fn get_ollama_models(store: tauri::State<'_, ChatStore>, http: tauri::State<'_, http::Http>) -> Result<Vec<OllamaModel>, String> {
     fn default_base() -> String { "http://localhost:11434".to_string() }
     let base = store
         .get_setting("ollama_base_url")
         .unwrap_or(None)
         .unwrap_or_else(default_base);
     let tags_url = format!("{}/api/tags", base.trim_end_matches('/'));
-    let client = reqwest::Client::new();
-    
-    match client
-        .get(&tags_url)
+
+    match http
+        .get(&tags_url, http::Profile::Metadata)
         .send()
         .await
     {
@@ -569,12 +683,15 @@ async fn get_ollama_models(store: tauri::State<'_, ChatStore>) -> Result<Vec<Oll
 }
 
 #[tauri::command]
-async fn get_openrouter_models() -> Result<Vec<OllamaModel>, String> {
+async fn get_openrouter_models(http: tauri::State<'_, http::Http>) -> Result<Vec<OllamaModel>, String> {
+    fetch_openrouter_models(&http).await
+}
+
+async fn fetch_openrouter_models(http: &http::Http) -> Result<Vec<OllamaModel>, String> {
     let api_key = read_openrouter_api_key()?;
-    let client = reqwest::Client::new();
     let url = "https://openrouter.ai/api/v1/models";
-    let resp = client
-        .get(url)
+    let resp = http
+        .get(url, http::Profile::Metadata)
         .header("Authorization", format!("Bearer {}", api_key))
         .send()
         .await
@@ -593,307 +710,232 @@ async fn get_openrouter_models() -> Result<Vec<OllamaModel>, String> {
     Ok(models)
 }
 
-async fn scrape_with_scrape_endpoint(url: &String, client: &reqwest::Client, api_key: &String) -> Result<String, String> {
-    info!("Önce /scrape deneniyor: {}", url);
-    let request_body = serde_json::json!({ "url": url });
-    let response = client
-        .post("https://api.firecrawl.dev/v0/scrape")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request_body)
-        .send()
-        .await
-        .map_err(|e| format!("/scrape isteği başarısız: {}", e))?;
-
-    let status = response.status();
-    let text = response.text().await.map_err(|e| format!("/scrape yanıtı okunamadı: {}", e))?;
-    info!("/scrape durumu: {}, yanıt (ilk 200): {}", status, &text[..text.len().min(200)]);
-
-    if status.is_success() {
-        let json_res: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("/scrape JSON parse hatası: {}", e))?;
-        if let Some(data) = json_res.get("data") {
-            if let Some(markdown) = data.get("markdown").and_then(|m| m.as_str()) {
-                if !markdown.trim().is_empty() {
-                    return Ok(markdown.to_string());
+// Simple HTTP fetch fallback (Firecrawl alternatifi)
+async fn simple_http_fetch(url: &str, cookie: Option<&str>) -> Result<ScrapedPage, String> {
+    let response = polite::get_with_cookies(url, http::Profile::Scraper, cookie).await?;
+    page_from_response(strategy::STEP_HTTP_FALLBACK, url, response, cookie.is_some()).await
+}
+
+// Kendi HTTP adımlarımızın yanıttan çıkarımı; yeniden doğrulamada 200 gelen yanıt da buradan geçer.
+// PDF ve akışlar her iki adımda aynı işlenir; HTML aggressive_html'de özetlenir, http_fallback'te
+// olduğu gibi Markdown'a çevrilir.
+async fn page_from_response(step: &str, url: &str, response: reqwest::Response, cookies: bool) -> Result<ScrapedPage, String> {
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let validators = page_cache::Validators::from_headers(response.headers());
+    let page = match read_body(response, url).await? {
+        FetchedBody::Pdf(bytes) => pdf_page(url, bytes).await?,
+        FetchedBody::Feed(bytes) => feed_page(url, &bytes)?,
+        FetchedBody::Html(decoded) => {
+            let links = links::from_html(&decoded.text, url);
+            let meta = metadata::PageMetadata::from_html(&decoded.text, url);
+            let images = images::candidates(&decoded.text, url);
+            let (content, encoding) = if step == strategy::STEP_AGGRESSIVE_HTML {
+                let (content, extracted, encoding) = aggressive_html_extract(url, decoded).await;
+                if !extracted {
+                    return Err("sayfadan anlamlı içerik çıkarılamadı".to_string());
                 }
-            }
+                info!("Agresif HTML çıkarımı: {} ({} via {})", url, encoding.name, encoding.via);
+                (content, encoding)
+            } else {
+                // Başlık, liste, bağlantı, tablo ve kod bloklarını koruyarak Markdown'a çevir
+                let markdown = markdown::html_to_markdown(&decoded.text, Some(url));
+                if markdown.trim().is_empty() {
+                    return Err("HTTP yanıtından içerik çıkarılamadı".to_string());
+                }
+                (markdown, decoded.encoding)
+            };
+            ScrapedPage::new(content, step).with_encoding(encoding).with_links(links).with_images(images).with_metadata(meta)
         }
-    }
-    
-    // /scrape başarısız olursa, hatayı /crawl'a geçmek için sinyal olarak kullan.
-    Err(format!("/scrape başarısız oldu veya içerik boş. Durum: {}", status))
+    };
+    Ok(page.with_validators(validators).with_cookies(cookies))
 }
 
+// Zincirdeki bir adımın sonucu: içerik ya da adımın bu sayfa için uygulanamama nedeni
+enum StepOutcome {
+    Page(Box<ScrapedPage>),
+    Skipped(&'static str),
+}
 
-async fn scrape_with_crawl_endpoint(url: &String, client: &reqwest::Client, api_key: &String) -> Result<String, String> {
-    info!("/scrape başarısız oldu, /crawl deneniyor: {}", url);
-    
-    // Adım 1: Crawl işini başlat
-    let crawl_request_body = serde_json::json!({ "url": url });
-    let crawl_response = client
-        .post("https://api.firecrawl.dev/v0/crawl")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&crawl_request_body)
-        .send()
-        .await
-        .map_err(|e| format!("Firecrawl /crawl isteği başarısız: {}", e))?;
-
-    if !crawl_response.status().is_success() {
-        return Err(format!("Firecrawl /crawl başlatma hatası: {}", crawl_response.status()));
+impl StepOutcome {
+    fn page(page: ScrapedPage) -> Self {
+        StepOutcome::Page(Box::new(page))
     }
-    
-    let crawl_response_json: serde_json::Value = crawl_response.json().await.map_err(|e| format!("Firecrawl /crawl yanıtı JSON'a çevrilemedi: {}", e))?;
-    let job_id = crawl_response_json["jobId"].as_str().ok_or("Crawl yanıtında jobId bulunamadı")?.to_string();
-    info!("Crawl işi başlatıldı, jobId: {}", job_id);
-
-    // Adım 2: Durumu kontrol et
-    let max_retries = 20;
-    for i in 0..max_retries {
-        info!("Crawl durumu kontrol ediliyor... Deneme {}", i + 1);
-        let status_url = format!("https://api.firecrawl.dev/v0/crawl/status/{}", job_id);
-        let status_response = client
-            .get(&status_url)
-            .header("Authorization", format!("Bearer {}", api_key))
-            .send()
-            .await
-            .map_err(|e| format!("Firecrawl durum kontrolü başarısız: {}", e))?;
+}
 
-        if !status_response.status().is_success() {
-            // Durum kontrolü geçici olarak başarısız olabilir, beklemeye devam et
-            warn!("Durum kontrolü geçici hata verdi: {}. Tekrar denenecek.", status_response.status());
-            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+// Alan adına göre yapılandırılmış adım zincirini sırayla çalıştırır. Her adım kendi zaman aşımıyla
+// sınırlanır; çalışan/atlanan/kapalı her adım deneme raporuna (ScrapedPage.attempts) yazılır.
+// `cookie` yalnızca sayfanın kendi sunucusuna giden HTTP adımlarında kullanılır.
+async fn scrape_page_content(window: &tauri::Window, state: &AppState, url: String, firecrawl_config: &firecrawl::FirecrawlConfig, steps: &[strategy::StepConfig], cookie: Option<&str>) -> Result<ScrapedPage, String> {
+    let mut attempts = Vec::new();
+    for step in steps {
+        if !step.enabled {
+            attempts.push(strategy::Attempt::disabled(&step.name));
             continue;
         }
-
-        let status_json: serde_json::Value = status_response.json().await.map_err(|e| format!("Firecrawl durum yanıtı JSON'a çevrilemedi: {}", e))?;
-        if let Some(status) = status_json["status"].as_str() {
-            match status {
-                "completed" => {
-                    info!("Crawl tamamlandı!");
-                    if let Some(data) = status_json.get("data") {
-                        if let Some(markdown) = data.get("markdown").and_then(|m| m.as_str()) {
-                            if !markdown.trim().is_empty() { return Ok(markdown.to_string()); }
-                        }
-                    }
-                    return Err("Crawl tamamlandı ancak içerik alınamadı.".to_string());
-                },
-                "crawling" => {
-                    info!("Crawl devam ediyor...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                },
-                "failed" => return Err(format!("Firecrawl işi başarısız oldu: {}", status_json["error"].as_str().unwrap_or("Bilinmeyen hata"))),
-                _ => return Err(format!("Bilinmeyen crawl durumu: {}", status)),
+        let started = Instant::now();
+        let result = match tokio::time::timeout(step.timeout(), run_strategy_step(window, state, &step.name, &url, firecrawl_config, cookie)).await {
+            Ok(r) => r,
+            Err(_) => {
+                warn!("'{}' adımı {} sn içinde bitmedi - sonraki adıma geçiliyor.", step.name, step.timeout_secs);
+                attempts.push(strategy::Attempt::timed_out(&step.name, started, step.timeout()));
+                continue;
             }
-        } else {
-            return Err("Durum yanıtında 'status' alanı bulunamadı.".to_string());
-        }
-    }
-    
-    Err("Firecrawl işi zaman aşımına uğradı.".to_string())
-}
-
-
-// Simple HTTP fetch fallback (Firecrawl alternatifi)
-async fn simple_http_fetch(url: &str) -> Result<String, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build().map_err(|e| format!("HTTP client oluşturulamadı: {}", e))?;
-    
-    let response = client.get(url).send().await.map_err(|e| format!("HTTP isteği başarısız: {}", e))?;
-    if !response.status().is_success() {
-        return Err(format!("HTTP {}", response.status()));
-    }
-    
-    let html = response.text().await.map_err(|e| format!("HTTP yanıt okunamadı: {}", e))?;
-    
-    // Basit HTML temizleme (script/style etiketlerini kaldır)
-    let mut clean = html.clone();
-    // Regex kullanmak için önce basit string replace yapalım
-    let patterns = [
-        ("<script", "</script>"),
-        ("<style", "</style>"),
-        ("<!--", "-->"),
-    ];
-    
-    for (start, end) in patterns {
-        while let Some(start_pos) = clean.find(start) {
-            if let Some(end_pos) = clean[start_pos..].find(end) {
-                let full_end = start_pos + end_pos + end.len();
-                clean.replace_range(start_pos..full_end, " ");
-            } else {
-                break;
+        };
+        match result {
+            Ok(StepOutcome::Page(page)) => {
+                let page = (*page).cleaned();
+                info!("'{}' adımı başarılı: {} ({} char)", step.name, url, page.content.len());
+                attempts.push(strategy::Attempt::finished(&step.name, started, Ok(())));
+                return Ok(page.with_attempts(attempts));
             }
-        }
-    }
-    
-    // HTML etiketlerini kaldır (basit yöntem)
-    let mut result = String::new();
-    let mut in_tag = false;
-    for ch in clean.chars() {
-        if ch == '<' { in_tag = true; }
-        else if ch == '>' { in_tag = false; }
-        else if !in_tag { result.push(ch); }
-    }
-    
-    // Fazla boşlukları temizle
-    let words: Vec<&str> = result.split_whitespace().collect();
-    Ok(words.join(" "))
-}
-
-// İçerik + kaynak etiketi döndürür: (content, source_label)
-async fn scrape_page_content(url: String) -> Result<(String, String), String> {
-    // Özel durum: YouTube sayfaları Firecrawl tarafından çoğunlukla engelleniyor.
-    // Bu durumda oEmbed + og:meta etiketlerinden hafif bir özet dene.
-    if is_youtube_url(&url) {
-        match scrape_youtube_light(&url).await {
-            Ok(md) => return Ok((md, "youtube_light".to_string())),
+            Ok(StepOutcome::Skipped(reason)) => attempts.push(strategy::Attempt::skipped(&step.name, reason)),
             Err(e) => {
-                warn!("YouTube özel scraper başarısız: {} - genel akışa devam.", e);
+                warn!("'{}' adımı başarısız: {} - sonraki adıma geçiliyor.", step.name, e);
+                attempts.push(strategy::Attempt::finished(&step.name, started, Err(e)));
             }
         }
     }
-
-    // Önce Firecrawl dene
-    if let Ok(api_key) = read_api_key() {
-        let client = reqwest::Client::new();
-        // Önce hızlı olan /scrape'i dene
-        match scrape_with_scrape_endpoint(&url, &client, &api_key).await {
-            Ok(markdown) => return Ok((markdown, "firecrawl_scrape".to_string())),
-            Err(e) => {
-                warn!("Firecrawl /scrape başarısız: {} - /crawl deneniyor.", e);
-                // /crawl dene
-                match scrape_with_crawl_endpoint(&url, &client, &api_key).await {
-                    Ok(markdown) => return Ok((markdown, "firecrawl_crawl".to_string())),
-                    Err(e2) => {
-                        warn!("Firecrawl /crawl de başarısız: {} - HTTP fallback.", e2);
-                    }
-                }
-            }
+    Err(strategy::failure_summary(&attempts))
+}
+
+// Yarış modu: açık adımlar aynı anda başlatılır. Kesin bir sonuç (DECISIVE_SCORE) gelirse hemen,
+// kullanılabilir bir sonuç gelirse kısa bir ek süre (RACE_GRACE) sonra, en geç süre dolunca en yüksek
+// puanlı sonuç seçilir. Bitmemiş adımların future'ları düşürülür; böylece istekleri de iptal olur.
+async fn race_page_content(window: &tauri::Window, state: &AppState, url: String, firecrawl_config: &firecrawl::FirecrawlConfig, steps: &[strategy::StepConfig], race: &strategy::RaceOptions, cookie: Option<&str>) -> Result<ScrapedPage, String> {
+    let started = Instant::now();
+    let mut deadline = tokio::time::Instant::now() + race.deadline();
+    let mut attempts = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut running = futures::stream::FuturesUnordered::new();
+    // Firecrawl crawl ücretli ve yavaş: yarışa girmez, yalnızca diğer adımların hiçbiri sayfa
+    // getiremezse son çare olarak çalışır
+    let mut fallback = None;
+    for step in steps {
+        if !step.enabled {
+            attempts.push(strategy::Attempt::disabled(&step.name));
+            continue;
         }
-    } else {
-        warn!("Firecrawl API anahtarı yok - HTTP fallback.");
-    }
-    
-    // Firecrawl başarısız olduysa: agresif HTML çıkarımı dene
-    if let Ok((content, extracted)) = aggressive_html_extract(&url).await {
-        if extracted {
-            info!("Agresif HTML çıkarımı başarılı: {} ({} char)", url, content.len());
-            return Ok((content, "aggressive_html".to_string()));
+        if step.name == strategy::STEP_FIRECRAWL_CRAWL {
+            fallback = Some(step);
+            continue;
         }
+        pending.push(&step.name);
+        let url = url.as_str();
+        running.push(async move {
+            let result = tokio::time::timeout(step.timeout(), run_strategy_step(window, state, &step.name, url, firecrawl_config, cookie)).await;
+            (step, result)
+        });
     }
 
-    // Firecrawl başarısız - basit HTTP fetch kullan
-    info!("HTTP fallback ile sayfa çekiliyor: {}", url);
-    simple_http_fetch(&url).await.map(|c| (c, "http_fallback".to_string()))
-}
-
-fn is_youtube_url(url: &str) -> bool {
-    // Alan adını sağlam şekilde kontrol et
-    if let Ok(parsed) = url::Url::parse(url) {
-        if let Some(host) = parsed.host_str() {
-            let h = host.to_lowercase();
-            if h.ends_with("youtube.com") || h == "youtu.be" { return true; }
+    // (puan, adım, süre, sayfa)
+    let mut best: Option<(f64, &str, Duration, ScrapedPage)> = None;
+    while let Ok(Some((step, result))) = tokio::time::timeout_at(deadline, running.next()).await {
+        pending.retain(|name| *name != step.name);
+        match result {
+            Err(_) => attempts.push(strategy::Attempt::timed_out(&step.name, started, step.timeout())),
+            Ok(Err(e)) => attempts.push(strategy::Attempt::finished(&step.name, started, Err(e))),
+            Ok(Ok(StepOutcome::Skipped(reason))) => attempts.push(strategy::Attempt::skipped(&step.name, reason)),
+            Ok(Ok(StepOutcome::Page(page))) => {
+                let page = (*page).cleaned();
+                let score = strategy::score(&step.name, &page.content);
+                let elapsed = started.elapsed();
+                info!("Yarış: '{}' {} ms'de bitti (puan {:.2}, {} char)", step.name, elapsed.as_millis(), score, page.content.len());
+                if best.as_ref().map(|(b, ..)| score <= *b).unwrap_or(false) {
+                    attempts.push(strategy::Attempt::discarded(&step.name, elapsed, score));
+                    continue;
+                }
+                if let Some((s, name, d, _)) = best.take() {
+                    attempts.push(strategy::Attempt::discarded(name, d, s));
+                }
+                best = Some((score, step.name.as_str(), elapsed, page));
+                if score >= strategy::DECISIVE_SCORE { break; }
+                if score >= strategy::USABLE_SCORE {
+                    deadline = deadline.min(tokio::time::Instant::now() + strategy::RACE_GRACE);
+                }
+            }
         }
     }
-    // Fallback basit kontrol
-    let lu = url.to_lowercase();
-    lu.contains("youtube.com") || lu.contains("youtu.be/")
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct YoutubeOEmbed {
-    title: Option<String>,
-    author_name: Option<String>,
-    author_url: Option<String>,
-    thumbnail_url: Option<String>,
-}
-
-async fn scrape_youtube_light(url: &str) -> Result<String, String> {
-    // 1) OEmbed ile başlık/kanal bilgisi al
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/117.0 Safari/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build().map_err(|e| format!("HTTP client oluşturulamadı: {}", e))?;
-
-    let oembed_url = format!("https://www.youtube.com/oembed?url={}&format=json", url);
-    let mut title: Option<String> = None;
-    let mut author: Option<String> = None;
-
-    if let Ok(resp) = client.get(&oembed_url).send().await {
-        if resp.status().is_success() {
-            if let Ok(text) = resp.text().await {
-                if let Ok(oe) = serde_json::from_str::<YoutubeOEmbed>(&text) {
-                    title = oe.title;
-                    author = oe.author_name;
-                }
+    drop(running);
+    for name in pending {
+        info!("Yarış: '{}' iptal edildi", name);
+        attempts.push(strategy::Attempt::cancelled(name, started));
+    }
+
+    if let (None, Some(step)) = (&best, fallback) {
+        info!("Yarış sonuç vermedi - son çare: '{}'", step.name);
+        let step_started = Instant::now();
+        match tokio::time::timeout(step.timeout(), run_strategy_step(window, state, &step.name, &url, firecrawl_config, cookie)).await {
+            Err(_) => attempts.push(strategy::Attempt::timed_out(&step.name, step_started, step.timeout())),
+            Ok(Err(e)) => attempts.push(strategy::Attempt::finished(&step.name, step_started, Err(e))),
+            Ok(Ok(StepOutcome::Skipped(reason))) => attempts.push(strategy::Attempt::skipped(&step.name, reason)),
+            Ok(Ok(StepOutcome::Page(page))) => {
+                let page = (*page).cleaned();
+                let score = strategy::score(&step.name, &page.content);
+                best = Some((score, step.name.as_str(), step_started.elapsed(), page));
             }
         }
     }
 
-    // 2) Sayfa HTML'inden meta başlık/açıklama/öneri video çek
-    let (desc, og_title, site_name, first_video) = match client.get(url).send().await {
-        Ok(resp) if resp.status().is_success() => {
-            match resp.text().await {
-                Ok(html) => {
-                    let d = find_meta_og_description(&html);
-                    let t = find_meta_property(&html, "og:title");
-                    let s = find_meta_property(&html, "og:site_name");
-                    let v = find_first_watch_link(&html);
-                    (d, t, s, v)
-                },
-                Err(_) => (None, None, None, None),
+    match best {
+        Some((score, name, duration, page)) => {
+            attempts.push(strategy::Attempt::won(name, duration, score));
+            Ok(page.with_attempts(attempts))
+        }
+        None => Err(strategy::failure_summary(&attempts)),
+    }
+}
+
+async fn run_strategy_step(window: &tauri::Window, state: &AppState, name: &str, url: &str, firecrawl_config: &firecrawl::FirecrawlConfig, cookie: Option<&str>) -> Result<StepOutcome, String> {
+    match name {
+        // Açık sekmenin render edilmiş DOM'u (giriş yapılmış / JavaScript ile oluşan sayfalar)
+        strategy::STEP_LIVE_DOM => match active_tab_id(state) {
+            Some(tab_id) => scrape_live_dom(window, state, &tab_id).await.map(StepOutcome::page),
+            None => Ok(StepOutcome::Skipped("açık sekme yok")),
+        },
+        // Siteye özel çıkarıcılar (YouTube, PDF vb.)
+        strategy::STEP_EXTRACTORS => match extractors::registry().extract(url).await {
+            Some(result) => result.map(StepOutcome::page),
+            None => Ok(StepOutcome::Skipped("eşleşen çıkarıcı yok")),
+        },
+        // Üçüncü taraf servis: sekme çerezleri hiçbir zaman gönderilmez
+        strategy::STEP_FIRECRAWL_SCRAPE | strategy::STEP_FIRECRAWL_CRAWL => {
+            if !firecrawl_config.is_usable() {
+                return Ok(StepOutcome::Skipped("Firecrawl yapılandırılmamış"));
             }
+            let client = http::shared().client();
+            let markdown = if name == strategy::STEP_FIRECRAWL_SCRAPE {
+                firecrawl::scrape(url, &client, firecrawl_config).await?
+            } else {
+                firecrawl::crawl(url, &client, firecrawl_config).await?
+            };
+            Ok(StepOutcome::page(ScrapedPage::new(markdown, name)))
         }
-        _ => (None, None, None, None),
-    };
-
-    // 3) Basit markdown çıktısı oluştur
-    let mut out = String::new();
-    out.push_str("[YouTube Video]\n\n");
-    if let Some(t) = title.clone().or(og_title.clone()) { out.push_str(&format!("Başlık: {}\n", t)); }
-    if let Some(a) = author.clone() { out.push_str(&format!("Kanal: {}\n", a)); }
-    if author.is_none() {
-        if let Some(sn) = site_name.clone() { out.push_str(&format!("Site: {}\n", sn)); }
-    }
-    out.push_str(&format!("URL: {}\n\n", url));
-    if let Some(d) = desc.clone() {
-        out.push_str("Açıklama (kısa):\n");
-        out.push_str(&d);
-        out.push_str("\n");
+        // Gövdeyi çek; PDF ise sayfa sayfa metin, HTML ise agresif çıkarım
+        strategy::STEP_AGGRESSIVE_HTML => {
+            let response = polite::get_with_cookies(url, http::Profile::Scraper, cookie).await?;
+            page_from_response(name, url, response, cookie.is_some()).await.map(StepOutcome::page)
+        }
+        strategy::STEP_HTTP_FALLBACK => simple_http_fetch(url, cookie).await.map(StepOutcome::page),
+        other => Err(format!("Bilinmeyen adım: {}", other)),
     }
-    if let Some(v) = first_video.clone() { out.push_str(&format!("Örnek video: https://www.youtube.com/watch?v={}\n", v)); }
+}
 
-    if out.trim().is_empty() {
-        Err("YouTube içeriği çıkarılamadı".to_string())
-    } else {
-        Ok(out)
-    }
+// PDF ayrıştırma CPU yoğun ve bazı bozuk belgelerde panik üretebiliyor; ayrı thread'de çalıştırılır
+async fn pdf_page(url: &str, bytes: Vec<u8>) -> Result<ScrapedPage, String> {
+    info!("PDF belgesi algılandı: {} ({} byte)", url, bytes.len());
+    let content = tokio::task::spawn_blocking(move || pdf::extract_pdf(&bytes))
+        .await
+        .map_err(|e| format!("PDF ayrıştırıcısı çöktü: {}", e))??;
+    Ok(ScrapedPage::new(content, "pdf"))
 }
 
-fn find_meta_og_description(html: &str) -> Option<String> {
-    // Basit dize arama: property="og:description" ve content="..."
-    let key = "property=\"og:description\"";
-    let key_alt = "property='og:description'";
-    if let Some(idx) = html.find(key).or_else(|| html.find(key_alt)) {
-        // Tag başlangıcını geriye doğru ara
-        let start = html[..idx].rfind("<meta").unwrap_or(0);
-        let end = html[idx..].find('>').map(|p| idx + p).unwrap_or(html.len());
-        let segment = &html[start..end];
-        // content="..." değerini al
-        if let Some(val) = extract_attr_value(segment, "content") { return Some(val); }
-    }
-    // Alternatif: name="description"
-    let key2 = "name=\"description\"";
-    let key2_alt = "name='description'";
-    if let Some(idx) = html.find(key2).or_else(|| html.find(key2_alt)) {
-        let start = html[..idx].rfind("<meta").unwrap_or(0);
-        let end = html[idx..].find('>').map(|p| idx + p).unwrap_or(html.len());
-        let segment = &html[start..end];
-        if let Some(val) = extract_attr_value(segment, "content") { return Some(val); }
-    }
-    None
+// RSS/Atom/JSON Feed gövdesi ham XML yerine son kayıtların listesi olarak verilir
+fn feed_page(url: &str, bytes: &[u8]) -> Result<ScrapedPage, String> {
+    let feed = feeds::parse(bytes, url, feeds::DEFAULT_ENTRIES)?;
+    info!("Akış algılandı: {} ({}, {} kayıt)", url, feed.kind, feed.entries.len());
+    Ok(ScrapedPage::new(feed.render(), "feed"))
 }
 
 fn find_meta_property(html: &str, property: &str) -> Option<String> {
@@ -909,21 +951,6 @@ fn find_meta_property(html: &str, property: &str) -> Option<String> {
     None
 }
 
-fn find_first_watch_link(html: &str) -> Option<String> {
-    // Basit arama: watch?v=VIDEOID
-    if let Some(pos) = html.find("watch?v=") {
-        let rest = &html[pos + 8..];
-        // VideoID tipik olarak 11 karakter, ancak burada '&' veya '"' gelene kadar alalım
-        let mut id = String::new();
-        for ch in rest.chars() {
-            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' { id.push(ch); }
-            else { break; }
-            if id.len() >= 20 { break; } // güvenlik sınırı
-        }
-        if !id.is_empty() { return Some(id); }
-    }
-    None
-}
 fn extract_attr_value(tag_segment: &str, attr: &str) -> Option<String> {
     // attr="..."
     let pat1 = format!("{}=\"", attr);
@@ -950,163 +977,155 @@ fn html_unescape(s: &str) -> String {
 }
 
 // ---- Genel amaçlı agresif çıkarım ----
-async fn aggressive_html_extract(url: &str) -> Result<(String, bool), String> {
-    let html = match http_get_html(url).await {
-        Ok(h) => h,
-        Err(e) => return Err(e),
-    };
+async fn aggressive_html_extract(url: &str, decoded: charset::Decoded) -> (String, bool, charset::EncodingInfo) {
+    let (html, encoding) = (decoded.text, decoded.encoding);
+
+    let (mut out, body_len) = summarize_html(&html, url);
+    let amp_link = find_link_rel_href(&html, "amphtml");
+
+    // AMP varsa ve mevcut içerik kısa ise deneyelim
+    if out.len() < 800 && amp_link.is_some() {
+        if let Some(amp_url) = amp_link {
+            if let Ok(amp) = http_get_html(&amp_url).await {
+                let amp_body = markdown::main_content_markdown(&amp.text, Some(&amp_url));
+                let amp_body = readability::truncate_utf8(&amp_body, 12000);
+                if amp_body.len() > body_len {
+                    out.push_str("\n[AMP İçerik]\n");
+                    out.push_str(amp_body);
+                }
+            }
+        }
+    }
+
+    let extracted = out.trim().len() > 100; // anlamlı bir şey çıktı mı?
+    (out, extracted, encoding)
+}
 
+// Bundan kısa okunabilir gövde yok sayılır (yapılandırılmış veride tam makale gövdesi verilir)
+const MIN_READABLE_BYTES: usize = 500;
+
+// Elimizdeki HTML'den başlık/meta/yapılandırılmış veri/ana içerik özetini üretir: (çıktı, ana içerik uzunluğu)
+fn summarize_html(html: &str, url: &str) -> (String, usize) {
     let mut out = String::new();
 
+    // DOM ayrıştırma senkron yapılır; Html tipi Send olmadığı için await öncesinde bırakılır
+    let (title, headings, readable, structured, tables) = {
+        let doc = scraper::Html::parse_document(html);
+        let base = url::Url::parse(url).ok();
+        let title = readability::document_title(&doc);
+        let mut headings = Vec::new();
+        headings.extend(readability::tag_texts(&doc, "h1").into_iter().take(3));
+        headings.extend(readability::tag_texts(&doc, "h2").into_iter().take(3));
+        headings.extend(readability::tag_texts(&doc, "h3").into_iter().take(3));
+        let cx = markdown::Ctx { base: base.as_ref(), prune: true };
+        let main = readability::find_main_content(&doc);
+        let readable = main.map(|el| markdown::element_to_markdown(el, &cx)).unwrap_or_default();
+        // Okunabilir gövde varsa JSON-LD makale gövdesi kısaltılır; yoksa içeriğin kendisi odur
+        let structured = structured::render_section(&doc, readable.trim().len() < MIN_READABLE_BYTES);
+        let tables = tables::render_section(&doc, main, &cx);
+        (title, headings, readable, structured, tables)
+    };
+
     // Başlık
-    if let Some(title) = find_tag_text(&html, "title") {
-        out.push_str(&format!("# {}\n\n", title.trim()));
+    if let Some(title) = title {
+        out.push_str(&format!("# {}\n\n", title));
     }
 
-    // Canonical ve amphtml
-    if let Some(canon) = find_link_rel_href(&html, "canonical") {
+    // Canonical
+    if let Some(canon) = find_link_rel_href(html, "canonical") {
         out.push_str(&format!("Canonical: {}\n\n", canon));
     }
-    let amp_link = find_link_rel_href(&html, "amphtml");
 
     // Meta açıklamalar
-    if let Some(desc) = find_meta_property(&html, "og:description").or_else(|| find_meta_name(&html, "description")) {
+    if let Some(desc) = find_meta_property(html, "og:description").or_else(|| find_meta_name(html, "description")) {
         out.push_str("Özet:\n");
         out.push_str(&desc);
         out.push_str("\n\n");
     }
 
-    if let Some(site_name) = find_meta_property(&html, "og:site_name") {
+    if let Some(site_name) = find_meta_property(html, "og:site_name") {
         out.push_str(&format!("Site: {}\n\n", site_name));
     }
 
-    // JSON-LD denemesi
-    if let Some(ld_blocks) = find_json_ld_blocks(&html) {
-        for block in ld_blocks.into_iter().take(3) {
-            if let Ok(json) = serde_json::from_str::<serde_json::Value>(&block) {
-                let headline = json.get("headline").and_then(|v| v.as_str()).unwrap_or("");
-                let description = json.get("description").and_then(|v| v.as_str()).unwrap_or("");
-                let article_body = json.get("articleBody").and_then(|v| v.as_str()).unwrap_or("");
-                if !headline.is_empty() { out.push_str(&format!("Başlık (LD): {}\n\n", headline)); }
-                if !description.is_empty() {
-                    out.push_str("Açıklama (LD):\n");
-                    out.push_str(description);
-                    out.push_str("\n\n");
-                }
-                if !article_body.is_empty() {
-                    out.push_str("İçerik (LD):\n");
-                    out.push_str(article_body);
-                    out.push_str("\n\n");
-                }
-            }
-        }
+    // JSON-LD (@graph dahil) + microdata/RDFa
+    if !structured.is_empty() {
+        out.push_str(&structured);
+        out.push('\n');
     }
 
     // Başlıklar (h1-h3)
-    let mut headings = Vec::new();
-    headings.extend(find_all_tag_texts(&html, "h1").into_iter().take(3));
-    headings.extend(find_all_tag_texts(&html, "h2").into_iter().take(3));
-    headings.extend(find_all_tag_texts(&html, "h3").into_iter().take(3));
     if !headings.is_empty() {
         out.push_str("Başlıklar:\n");
         for h in headings { out.push_str(&format!("- {}\n", h)); }
         out.push_str("\n");
     }
 
-    // Paragraflar (ilk ~5000 karaktere kadar)
-    let mut body_acc = String::new();
-    for p in find_all_tag_texts(&html, "p") {
-        if body_acc.len() > 5000 { break; }
-        let t = p.trim();
-        if t.len() >= 20 { // çok kısa parçaları atla
-            body_acc.push_str(t);
-            body_acc.push_str("\n\n");
-        }
-    }
-    if !body_acc.is_empty() {
+    // Ana içerik (readability puanlaması ile seçilen düğüm)
+    let body = readability::truncate_utf8(&readable, 12000);
+    if !body.is_empty() {
         out.push_str("İçerik:\n");
-        out.push_str(&body_acc);
+        out.push_str(body);
+        out.push_str("\n\n");
     }
 
-    // AMP varsa ve mevcut içerik kısa ise deneyelim
-    if out.len() < 800 && amp_link.is_some() {
-        if let Some(amp_url) = amp_link {
-            if let Ok(amp_html) = http_get_html(&amp_url).await {
-                let mut amp_body = String::new();
-                for p in find_all_tag_texts(&amp_html, "p") {
-                    if amp_body.len() > 6000 { break; }
-                    let t = p.trim();
-                    if t.len() >= 20 { amp_body.push_str(t); amp_body.push_str("\n\n"); }
-                }
-                if amp_body.len() > body_acc.len() {
-                    out.push_str("\n[AMP İçerik]\n");
-                    out.push_str(&amp_body);
-                }
-            }
-        }
+    // Ana içerik dışında kalan veri tabloları (bilgi kutusu, fiyat/özellik tabloları)
+    if !tables.is_empty() {
+        out.push_str(&tables);
+        out.push('\n');
     }
 
-    let extracted = out.trim().len() > 100; // anlamlı bir şey çıktı mı?
-    Ok((out, extracted))
+    (out, body.len())
 }
 
-async fn http_get_html(url: &str) -> Result<String, String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/117.0 Safari/537.36")
-        .timeout(std::time::Duration::from_secs(10))
-        .build().map_err(|e| format!("HTTP client oluşturulamadı: {}", e))?;
-    let response = client.get(url).send().await.map_err(|e| format!("HTTP isteği başarısız: {}", e))?;
-    if !response.status().is_success() { return Err(format!("HTTP {}", response.status())); }
-    response.text().await.map_err(|e| format!("HTTP yanıt okunamadı: {}", e))
-}
-
-fn find_tag_text(html: &str, tag: &str) -> Option<String> {
-    let l = html.to_lowercase();
-    let open = format!("<{}", tag);
-    if let Some(mut start) = l.find(&open) {
-        // '>' konumunu bul
-        if let Some(gt) = l[start..].find('>') { start += gt + 1; } else { return None; }
-        let close = format!("</{}>", tag);
-        if let Some(end_rel) = l[start..].find(&close) {
-            let end = start + end_rel;
-            let inner = &html[start..end];
-            return Some(strip_tags(inner));
-        }
-    }
-    None
+// HTTP gövdesi: HTML/metin ise çözülmüş metin, PDF ya da RSS/Atom/JSON Feed ise ham baytlar
+enum FetchedBody {
+    Html(charset::Decoded),
+    Pdf(Vec<u8>),
+    Feed(Vec<u8>),
 }
 
-fn find_all_tag_texts(html: &str, tag: &str) -> Vec<String> {
-    let mut out = Vec::new();
-    let l = html.to_lowercase();
-    let open = format!("<{}", tag);
-    let close = format!("</{}>", tag);
-    let mut idx = 0;
-    while let Some(pos) = l[idx..].find(&open) {
-        let start_tag = idx + pos;
-        let after_tag = match l[start_tag..].find('>') { Some(p) => start_tag + p + 1, None => break };
-        if let Some(end_rel) = l[after_tag..].find(&close) {
-            let end = after_tag + end_rel;
-            let inner = &html[after_tag..end];
-            let text = strip_tags(inner).trim().to_string();
-            if !text.is_empty() { out.push(text); }
-            idx = end + close.len();
-        } else { break; }
+async fn http_get_html(url: &str) -> Result<charset::Decoded, String> {
+    match http_get_body(url).await? {
+        FetchedBody::Html(d) => Ok(d),
+        FetchedBody::Pdf(_) => Err("Yanıt HTML değil, PDF belgesi".to_string()),
+        FetchedBody::Feed(_) => Err("Yanıt HTML değil, RSS/Atom akışı".to_string()),
     }
-    out
 }
 
-fn strip_tags(html: &str) -> String {
-    let mut result = String::new();
-    let mut in_tag = false;
-    for ch in html.chars() {
-        if ch == '<' { in_tag = true; }
-        else if ch == '>' { in_tag = false; }
-        else if !in_tag { result.push(ch); }
+// robots.txt ve host başına hız sınırı polite katmanında uygulanır
+async fn http_get_body(url: &str) -> Result<FetchedBody, String> {
+    http_get_body_with_cookies(url, None).await.map(|(body, _)| body)
+}
+
+// Gövde + önbellek yeniden doğrulaması için yanıtın ETag / Last-Modified başlıkları
+async fn http_get_body_with_cookies(url: &str, cookie: Option<&str>) -> Result<(FetchedBody, page_cache::Validators), String> {
+    let response = polite::get_with_cookies(url, http::Profile::Scraper, cookie).await?;
+    if !response.status().is_success() { return Err(format!("HTTP {}", response.status())); }
+    let validators = page_cache::Validators::from_headers(response.headers());
+    Ok((read_body(response, url).await?, validators))
+}
+
+// Gövdeyi ham bayt olarak okur. PDF ya da akış ise (Content-Type / imza / kök öğe) olduğu gibi döner,
+// değilse kodlamayı header/meta/BOM/tespit sırasıyla belirleyip çözer.
+async fn read_body(response: reqwest::Response, url: &str) -> Result<FetchedBody, String> {
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    let bytes = response.bytes().await.map_err(|e| format!("HTTP yanıt okunamadı: {}", e))?;
+    if pdf::looks_like_pdf(content_type.as_deref(), &bytes) {
+        return Ok(FetchedBody::Pdf(bytes.to_vec()));
+    }
+    // Sitemap gibi diğer XML belgeleri HTML/metin olarak kalır (site taraması onları okur)
+    if feeds::looks_like_feed(content_type.as_deref(), &bytes) {
+        return Ok(FetchedBody::Feed(bytes.to_vec()));
     }
-    // Fazla boşluk temizliği
-    let parts: Vec<&str> = result.split_whitespace().collect();
-    parts.join(" ")
+    let decoded = charset::decode_html(&bytes, content_type.as_deref(), Some(url));
+    if decoded.encoding.had_errors {
+        warn!("Kodlama çözülürken geçersiz baytlar görüldü: {} ({} via {})", url, decoded.encoding.name, decoded.encoding.via);
+    }
+    Ok(FetchedBody::Html(decoded))
 }
 
 fn find_meta_name(html: &str, name: &str) -> Option<String> {
@@ -1138,33 +1157,633 @@ fn find_link_rel_href(html: &str, rel: &str) -> Option<String> {
     None
 }
 
-fn find_json_ld_blocks(html: &str) -> Option<Vec<String>> {
-    let mut out = Vec::new();
-    let mut idx = 0usize;
-    loop {
-        if let Some(pos) = html[idx..].find("<script") {
-            let start = idx + pos;
-            let end_tag = html[start..].find('>').map(|p| start + p).unwrap_or(html.len());
-            let segment = &html[start..end_tag];
-            if segment.contains("type=\"application/ld+json\"") || segment.contains("type='application/ld+json'") {
-                // Kapanışı bul
-                if let Some(close_pos) = html[end_tag..].find("</script>") {
-                    let content = &html[end_tag + 1..end_tag + close_pos];
-                    out.push(content.trim().to_string());
-                    idx = end_tag + close_pos + "</script>".len();
-                    continue;
-                } else { break; }
+// ---- Canlı DOM: açık sekmedeki render edilmiş belge ----
+fn active_tab_id(state: &AppState) -> Option<String> {
+    let tab = state.last_active_tab.lock().ok()?.clone()?;
+    let known = state.tab_ids.lock().map(|set| set.contains(&tab)).unwrap_or(false);
+    if known { Some(tab) } else { None }
+}
+
+async fn capture_live_dom(window: &tauri::Window, state: &AppState, tab_id: &str) -> Result<LiveDomSnapshot, String> {
+    let webview = window.get_webview(tab_id).ok_or_else(|| format!("Webview bulunamadı: {}", tab_id))?;
+
+    // Sayfanın kendiliğinden sahte DOM gönderememesi için tahmin edilemez, tek kullanımlık istek kimliği
+    let mut nonce = [0u8; 16];
+    getrandom::fill(&mut nonce).map_err(|e| format!("Rastgele istek kimliği üretilemedi: {}", e))?;
+    let request_id = hex::encode(nonce);
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    state.pending_dom.lock().map_err(|_| "dom lock".to_string())?.insert(request_id.clone(), PendingDom { tab_id: tab_id.to_string(), tx });
+    // Adım zaman aşımıyla future düşürülse de bekleyen istek temizlenir
+    let _pending = PendingGuard { state, request_id: &request_id };
+
+    let js = format!(r#"
+        (function() {{
+            try {{
+                const root = document.documentElement.cloneNode(true);
+                root.querySelectorAll('script, style, noscript, template').forEach(n => n.remove());
+                window.__TAURI__.core.invoke('submit_live_dom', {{
+                    tabId: '{}',
+                    requestId: '{}',
+                    url: window.location.href,
+                    html: '<!DOCTYPE html>' + root.outerHTML
+                }}).catch(console.error);
+            }} catch (e) {{ console.error(e); }}
+        }})();
+    "#, tab_id, request_id);
+    webview.eval(&js).map_err(|e| format!("Canlı DOM script'i çalıştırılamadı: {}", e))?;
+
+    match tokio::time::timeout(Duration::from_secs(5), rx).await {
+        Ok(Ok(snapshot)) => Ok(snapshot),
+        _ => Err("Canlı DOM yanıtı zaman aşımına uğradı".to_string()),
+    }
+}
+
+struct PendingGuard<'a> {
+    state: &'a AppState,
+    request_id: &'a str,
+}
+
+impl Drop for PendingGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut pending) = self.state.pending_dom.lock() { pending.remove(self.request_id); }
+    }
+}
+
+// Aktif sekmenin DOM'unu alıp aynı çıkarım hattından geçirir (zincirdeki live_dom adımı).
+async fn scrape_live_dom(window: &tauri::Window, state: &AppState, tab_id: &str) -> Result<ScrapedPage, String> {
+    let snapshot = capture_live_dom(window, state, tab_id).await?;
+    let (content, _) = summarize_html(&snapshot.html, &snapshot.url);
+    if content.trim().len() <= 100 {
+        return Err("Canlı DOM'dan anlamlı içerik çıkarılamadı".to_string());
+    }
+    info!("Canlı DOM çıkarımı başarılı: {} ({} char)", snapshot.url, content.len());
+    let links = links::from_html(&snapshot.html, &snapshot.url);
+    let meta = metadata::PageMetadata::from_html(&snapshot.html, &snapshot.url);
+    let images = images::candidates(&snapshot.html, &snapshot.url);
+    Ok(ScrapedPage::new(content, strategy::STEP_LIVE_DOM).with_links(links).with_images(images).with_metadata(meta))
+}
+
+// Yalnızca isteğin gönderildiği sekmenin kendisi yanıtlayabilir; başka bir sekme kimliği bilse bile
+// kendi etiketiyle eşleşmeyen isteği tüketemez.
+#[tauri::command]
+async fn submit_live_dom(webview: tauri::Webview, state: tauri::State<'_, AppState>, tab_id: String, request_id: String, url: String, html: String) -> Result<(), String> {
+    if webview.label() != tab_id {
+        return Err("DOM yanıtı başka bir sekmeden geldi".to_string());
+    }
+    let mut pending = state.pending_dom.lock().map_err(|_| "dom lock".to_string())?;
+    let entry = match pending.remove(&request_id) {
+        Some(entry) if entry.tab_id == tab_id => entry,
+        Some(entry) => {
+            pending.insert(request_id, entry);
+            return Err("DOM isteği bu sekme için verilmedi".to_string());
+        }
+        None => return Err("Bilinmeyen veya süresi dolmuş DOM isteği".to_string()),
+    };
+    drop(pending);
+    info!("Canlı DOM alındı: tab={} url={} ({} byte)", tab_id, url, html.len());
+    let _ = entry.tx.send(LiveDomSnapshot { url, html });
+    Ok(())
+}
+
+// Modele verilecek içerik: scope "site" ise site derlemi, değilse cache / scrape zinciri.
+// (sayfa, cache'ten mi) döner.
+async fn acquire_page(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str, scope: Option<&str>) -> Result<(ScrapedPage, bool), String> {
+    if scope == Some("site") {
+        return site_corpus_page(window, state, store, url).await;
+    }
+    // Akış bulunamazsa soru normal sayfa içeriğiyle cevaplanır
+    if scope == Some("feed") {
+        match feed_context_page(window, state, store, url).await {
+            Ok(page) => return Ok((page, false)),
+            Err(e) => warn!("Akış bağlamı alınamadı: {} - sayfa içeriğiyle devam.", e),
+        }
+    }
+
+    let (pattern, steps) = strategy::chain_for(store, url);
+
+    // Cache kontrolü: bellek, sonra kalıcı tablo (gerekirse koşullu GET ile yeniden doğrulanır).
+    // Zincirde canlı DOM açıksa sayfa her soruda açık sekmeden tazelenir; cache'e bakılmaz.
+    let cookie = session_cookie(window, state, store, url);
+    if !strategy::uses_live_dom(&steps) {
+        if let Some(hit) = cached_page(state, store, url, cookie.as_deref()).await {
+            return Ok(hit);
+        }
+    }
+
+    info!("İçerik zinciri ({}): {}", pattern, steps.iter().filter(|s| s.enabled).map(|s| s.name.as_str()).collect::<Vec<_>>().join(" -> "));
+    let firecrawl_config = firecrawl::FirecrawlConfig::load(store);
+    let race = strategy::RaceOptions::load(store);
+    let fresh = if race.enabled {
+        race_page_content(window, state, url.to_string(), &firecrawl_config, &steps, &race, cookie.as_deref()).await?
+    } else {
+        scrape_page_content(window, state, url.to_string(), &firecrawl_config, &steps, cookie.as_deref()).await?
+    };
+    Ok((remember_page(state, store, url, fresh), false))
+}
+
+// Yeni çekilen sayfayı eksik alanlarını tamamlayıp belleğe ve (çerezsizse) kalıcı cache'e yazar
+fn remember_page(state: &AppState, store: &ChatStore, url: &str, mut fresh: ScrapedPage) -> ScrapedPage {
+    // HTML'i elimizde olmayan kaynaklarda (Firecrawl, çıkarıcılar) bağlantılar Markdown'dan alınır
+    if fresh.links.is_empty() { fresh.links = links::from_markdown(&fresh.content, url); }
+    if fresh.images.is_empty() { fresh.images = images::from_markdown(&fresh.content, url); }
+    if fresh.metadata.is_none() { fresh.metadata = Some(metadata::PageMetadata::from_text(&fresh.content)); }
+    // Canlı DOM anlık görüntüdür, cache'e yazılmaz
+    if fresh.source == strategy::STEP_LIVE_DOM {
+        return fresh;
+    }
+    // Belleğe ve kalıcı cache'e yaz. Sekme çerezleriyle çekilen (oturumlu) sayfa diske yazılmaz.
+    if fresh.cookies {
+        // Aynı adresin eski, çerezsiz kaydı artık geçerli değil
+        if let Err(e) = store.cache_delete(url) { warn!("{}", e); }
+    } else {
+        let record = page_cache::Record {
+            url: url.to_string(),
+            content: fresh.content.clone(),
+            source: fresh.source.clone(),
+            content_hash: page_cache::content_hash(&fresh.content),
+            validators: fresh.validators.clone(),
+            fetched_at: chrono::Utc::now().timestamp(),
+            links: fresh.links.clone(),
+            images: fresh.images.clone(),
+            metadata: fresh.metadata.clone(),
+            cookies: false,
+        };
+        if let Err(e) = store.cache_put(&record) { warn!("Sayfa cache'e yazılamadı: {}", e); }
+        prune_stored_cache(state, store);
+    }
+    if let Ok(mut cache) = state.page_cache.lock() {
+        cache.insert(url, fresh.clone());
+    }
+    fresh
+}
+
+// TTL içindeki kayıt doğrudan; süresi dolmuş ama doğrulayıcısı olan kayıt 304 alırsa yeniden çıkarım
+// yapılmadan kullanılır, 200 alırsa aynı yanıttan yeniden çıkarılıp cache'e yazılır. Firecrawl / çıkarıcı
+// kaynaklı kayıtların doğrulayıcısı yoktur, yeniden çekilir. Sekme çerezleri kullanılacaksa yalnızca
+// bellekteki oturumlu kopya geçerlidir: kalıcı kayıtlar çerezsiz (çoğu zaman giriş duvarı) içeriktir.
+// (sayfa, cache'ten mi) döner.
+async fn cached_page(state: &AppState, store: &ChatStore, url: &str, cookie: Option<&str>) -> Option<(ScrapedPage, bool)> {
+    if let Ok(mut cache) = state.page_cache.lock() {
+        if let Some(page) = cache.get(url, cookie.is_some()) {
+            info!("Cache hit (bellek): URL içeriği TTL içinde. Yeniden scrape edilmeyecek.");
+            return Some((page, true));
+        }
+    }
+    if cookie.is_some() { return None; }
+    match stored_page(store, url).await? {
+        Stored::Hit(page) => {
+            if let Ok(mut cache) = state.page_cache.lock() {
+                cache.record_stored_hit();
+                cache.insert(url, page.clone());
+            }
+            Some((page, true))
+        }
+        Stored::Refetched(page) => Some((remember_page(state, store, url, page), false)),
+    }
+}
+
+// Kalıcı kayıttan sonuç: taze/304 kayıt ya da değişmiş sayfanın yeniden doğrulama yanıtından çıkarılmış hali
+enum Stored {
+    Hit(ScrapedPage),
+    Refetched(ScrapedPage),
+}
+
+// Kalıcı tabloyu bellek katmanıyla aynı sınırlara indirir
+fn prune_stored_cache(state: &AppState, store: &ChatStore) {
+    let Ok(mut cache) = state.page_cache.lock() else { return };
+    match store.cache_prune(&cache.config()) {
+        Ok(0) => {}
+        Ok(n) => {
+            info!("Kalıcı sayfa cache'inden {} eski kayıt silindi", n);
+            cache.record_evictions(n);
+        }
+        Err(e) => warn!("Kalıcı sayfa cache'i küçültülemedi: {}", e),
+    }
+}
+
+async fn stored_page(store: &ChatStore, url: &str) -> Option<Stored> {
+    let record = match store.cache_get(url) {
+        Ok(r) => r?,
+        Err(e) => {
+            warn!("Sayfa cache'i okunamadı: {}", e);
+            return None;
+        }
+    };
+    // Oturumlu sayfalar diske yazılmaz; eski sürümlerden kalmış kayıt kullanılmaz
+    if record.cookies {
+        let _ = store.cache_delete(url);
+        return None;
+    }
+    if !record.is_fresh() {
+        if record.validators.is_empty() {
+            info!("Cache expired: doğrulayıcı yok ({}), yeniden scrape edilecek.", record.source);
+            return None;
+        }
+        let started = Instant::now();
+        match page_cache::revalidate(url, &record.validators).await {
+            Ok(page_cache::Revalidation::NotModified(validators)) => {
+                if let Err(e) = store.cache_touch(url, &validators) { warn!("{}", e); }
+            }
+            Ok(page_cache::Revalidation::Modified(response)) => {
+                // Kaydı üreten HTTP adımıyla aynı çıkarım (PDF / akış kayıtları gövdeden tanınır)
+                let step = if record.source == strategy::STEP_HTTP_FALLBACK { strategy::STEP_HTTP_FALLBACK } else { strategy::STEP_AGGRESSIVE_HTML };
+                return match page_from_response(step, url, response, false).await {
+                    Ok(page) => {
+                        let attempt = strategy::Attempt::finished(step, started, Ok(()));
+                        Some(Stored::Refetched(page.cleaned().with_attempts(vec![attempt])))
+                    }
+                    Err(e) => {
+                        warn!("Değişen sayfa çıkarılamadı: {} - yeniden scrape edilecek.", e);
+                        None
+                    }
+                };
+            }
+            Err(e) => {
+                warn!("{} - yeniden scrape edilecek.", e);
+                return None;
+            }
+        }
+    } else {
+        info!("Cache hit (kalıcı): {} ({})", url, record.source);
+    }
+    let mut page = ScrapedPage::new(record.content, &record.source)
+        .with_links(record.links)
+        .with_images(record.images)
+        .with_validators(record.validators)
+        .with_cookies(record.cookies);
+    page.metadata = record.metadata;
+    Some(Stored::Hit(page))
+}
+
+// Çerez paylaşımına izin verilmiş sitelerde sekmenin çerez deposundan Cookie başlığı; aksi halde None
+fn session_cookie(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str) -> Option<String> {
+    if !cookies::CookieSharing::load(store).allows(url) { return None; }
+    let tab_id = active_tab_id(state).or_else(|| state.tab_ids.lock().ok()?.iter().next().cloned())?;
+    let webview = window.get_webview(&tab_id)?;
+    match cookies::header_for(&webview, url) {
+        Ok(Some(header)) => {
+            info!("Sekme çerezleri kullanılıyor: {} ({} çerez)", url, header.matches("; ").count() + 1);
+            Some(header)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            warn!("{}", e);
+            None
+        }
+    }
+}
+
+// "Neler yeni?" bağlamı: adres akışın kendisiyse doğrudan, değilse sayfada keşfedilen ilk akışın son kayıtları
+async fn feed_context_page(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str) -> Result<ScrapedPage, String> {
+    let html = match http_get_body(url).await {
+        Ok(FetchedBody::Feed(bytes)) => return feed_page(url, &bytes),
+        Ok(FetchedBody::Html(decoded)) => decoded.text,
+        // Oturum gerektiren sayfalarda açık sekmenin DOM'u denenir
+        _ => page_html(window, state, store, url).await?,
+    };
+    let links = feeds::discover(&html, url);
+    let link = links.first().ok_or_else(|| "Sayfada RSS/Atom akışı bulunamadı".to_string())?;
+    info!("Akış keşfedildi: {} -> {} ({})", url, link.url, link.media_type);
+    match http_get_body(&link.url).await? {
+        FetchedBody::Feed(bytes) => feed_page(&link.url, &bytes),
+        _ => Err(format!("Keşfedilen adres akış değil: {}", link.url)),
+    }
+}
+
+// Tek sayfa için model bağlam sınırı; site derlemi birden çok sayfayı taşıdığı için daha geniş
+const PAGE_CONTEXT_BYTES: usize = 8000;
+const SITE_CONTEXT_BYTES: usize = 24000;
+const SITE_CORPUS_TTL: Duration = Duration::from_secs(30 * 60);
+const MAX_SITE_CORPORA: usize = 4; // bellekte tutulan derlem sayısı (her biri 100 sayfaya kadar)
+// Sekmedeki akış görünümünde listelenecek kayıt sayısı
+const FEED_PREVIEW_ENTRIES: usize = 50;
+
+fn context_budget(page: &ScrapedPage) -> usize {
+    if page.source.starts_with("site_") { SITE_CONTEXT_BYTES } else { PAGE_CONTEXT_BYTES }
+}
+
+// Modele giden bağlam: sayfa bilgileri + bütçeye sığan içerik + numaralı bağlantı eki
+// (bilgiler ve ek kesilmesin diye bütçeden düşülür)
+fn page_context(page: &ScrapedPage) -> String {
+    let header = page.metadata.as_ref().map(|m| format!("{}\n", m.render())).unwrap_or_default();
+    let appendix = links::render_appendix(&page.links);
+    let budget = context_budget(page).saturating_sub(header.len() + appendix.len());
+    let mut context = header;
+    context.push_str(readability::truncate_utf8(&page.content, budget));
+    if !appendix.is_empty() {
+        context.push_str("\n\n");
+        context.push_str(&appendix);
+    }
+    context
+}
+
+// Cevaptaki [L3] atıflarını gerçek adreslere çevirir; atıf varsa frontend mesajı yeniden çizer
+fn resolve_answer_links(window: &tauri::Window, page: &ScrapedPage, answer: String) -> String {
+    let (resolved, cited) = links::resolve_refs(&answer, &page.links);
+    if cited.is_empty() { return answer; }
+    info!("Cevapta {} bağlantı atfı çözüldü", cited.len());
+    window.emit("answer-links", &serde_json::json!({ "text": resolved, "links": cited })).ok();
+    resolved
+}
+
+fn site_key(url: &str) -> Result<String, String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Geçersiz URL: {}", e))?;
+    Ok(parsed.origin().ascii_serialization())
+}
+
+// Sitenin derlemini cache'ten verir; yoksa kayıtlı seçeneklerle tarar
+async fn site_corpus_page(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str) -> Result<(ScrapedPage, bool), String> {
+    let key = site_key(url)?;
+    let cached = state.site_corpora.lock().ok().and_then(|map| {
+        map.get(&key).filter(|c| corpus_is_fresh(c)).cloned()
+    });
+    let (corpus, from_cache) = match cached {
+        Some(c) => (c, true),
+        None => (run_site_crawl(window, state, store, url, &crawl::CrawlOptions::load(store)).await?, false),
+    };
+    let page = ScrapedPage::new(corpus.render(SITE_CONTEXT_BYTES), corpus.source_label());
+    Ok((page, from_cache))
+}
+
+fn corpus_is_fresh(corpus: &crawl::SiteCorpus) -> bool {
+    corpus.built_at.map(|t| t.elapsed() < SITE_CORPUS_TTL).unwrap_or(false)
+}
+
+async fn run_site_crawl(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str, options: &crawl::CrawlOptions) -> Result<crawl::SiteCorpus, String> {
+    let key = site_key(url)?;
+    let fc = firecrawl::FirecrawlConfig::load(store);
+    let progress_window = window.clone();
+    let progress = move |done: usize, total: usize, page_url: &str| {
+        progress_window.emit("crawl-progress", &serde_json::json!({ "done": done, "total": total, "url": page_url })).ok();
+    };
+    let corpus = crawl::crawl_site(url, options, &fc, &progress).await?;
+    // Süresi dolan derlemler atılır; sınır aşılırsa en eski derlem yer açar
+    if let Ok(mut map) = state.site_corpora.lock() {
+        map.retain(|k, c| *k != key && corpus_is_fresh(c));
+        while map.len() >= MAX_SITE_CORPORA {
+            let Some(oldest) = map.iter().min_by_key(|(_, c)| c.built_at).map(|(k, _)| k.clone()) else { break };
+            map.remove(&oldest);
+        }
+        map.insert(key, corpus.clone());
+    }
+    Ok(corpus)
+}
+
+#[tauri::command]
+async fn crawl_site(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, url: String, options: Option<crawl::CrawlOptions>) -> Result<crawl::SiteCorpus, String> {
+    let options = options.unwrap_or_else(|| crawl::CrawlOptions::load(&store));
+    options.validate()?;
+    run_site_crawl(&window, &state, &store, &url, &options).await
+}
+
+// Dışa aktarılan tablo: data, istenen biçime göre CSV metni ya da JSON kayıt dizisi
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TableExport {
+    index: usize,
+    caption: Option<String>,
+    columns: usize,
+    rows: usize,
+    data: String,
+}
+
+// Sayfanın ham HTML'i: zincirde live_dom açıksa açık sekmenin DOM'u, değilse HTTP ile alınan HTML
+async fn page_html(window: &tauri::Window, state: &AppState, store: &ChatStore, url: &str) -> Result<String, String> {
+    if strategy::uses_live_dom(&strategy::chain_for(store, url).1) {
+        if let Some(tab_id) = active_tab_id(state) {
+            match capture_live_dom(window, state, &tab_id).await {
+                Ok(snapshot) => return Ok(snapshot.html),
+                Err(e) => warn!("Canlı DOM alınamadı: {} - HTTP ile devam.", e),
             }
-            idx = end_tag + 1;
-        } else { break; }
+        }
+    }
+    Ok(http_get_html(url).await?.text)
+}
+
+// Görsel destekli modeller için sayfa görselleri: çıkarım sırasında sayfanın kendi HTML'inden (sekme
+// çerezleriyle çekildiyse oturumlu halinden) toplanan adaylar indirilir; sayfa yeniden çekilmez
+async fn page_images(page: &ScrapedPage) -> Vec<images::PageImage> {
+    images::collect(&page.images).await
+}
+
+// Sekmeye her yüklemede enjekte edilir; belge RSS/Atom (XML kök öğesi) ya da JSON Feed değilse hiçbir şey yapmaz.
+// Öğeler XHTML ad alanında oluşturulur (XML belgelerinde HTML işlenmesi için) ve metinler textContent ile yazılır.
+const FEED_VIEW_JS: &str = r#"
+    (function() {
+        if (window.__feed_view_injected) return;
+        window.__feed_view_injected = true;
+        const doc = document;
+        const root = doc.documentElement ? doc.documentElement.nodeName.toLowerCase() : '';
+        const type = (doc.contentType || '').toLowerCase();
+        const isXmlFeed = root === 'rss' || root === 'feed' || root === 'rdf:rdf';
+        const isJsonFeed = type.includes('json') && !!doc.body && (doc.body.textContent || '').includes('jsonfeed.org/version');
+        if (!isXmlFeed && !isJsonFeed) return;
+        const NS = 'http://www.w3.org/1999/xhtml';
+        const el = (tag, text) => {
+            const e = doc.createElementNS(NS, tag);
+            if (text) e.textContent = text;
+            return e;
+        };
+        window.__TAURI__.core.invoke('feed_preview', { url: window.location.href }).then(feed => {
+            const body = el('body');
+            body.setAttribute('style', 'font: 15px/1.5 -apple-system, system-ui, sans-serif; max-width: 760px; margin: 32px auto; padding: 0 16px; color: #222;');
+            body.appendChild(el('h1', feed.title || feed.url));
+            if (feed.description) body.appendChild(el('p', feed.description));
+            const meta = el('p', feed.kind.toUpperCase() + ' akışı · ' + feed.entries.length + ' kayıt');
+            meta.setAttribute('style', 'color: #777; font-size: 13px;');
+            body.appendChild(meta);
+            for (const entry of feed.entries) {
+                const item = el('article');
+                item.setAttribute('style', 'border-top: 1px solid #eee; padding: 12px 0;');
+                const title = el('h3');
+                title.setAttribute('style', 'margin: 0 0 4px;');
+                if (entry.link) {
+                    const a = el('a', entry.title);
+                    a.setAttribute('href', entry.link);
+                    title.appendChild(a);
+                } else {
+                    title.textContent = entry.title;
+                }
+                item.appendChild(title);
+                if (entry.date) {
+                    const date = el('div', new Date(entry.date).toLocaleString());
+                    date.setAttribute('style', 'color: #777; font-size: 13px;');
+                    item.appendChild(date);
+                }
+                if (entry.summary) item.appendChild(el('p', entry.summary));
+                body.appendChild(item);
+            }
+            if (isXmlFeed) {
+                const html = el('html');
+                const head = el('head');
+                head.appendChild(el('title', feed.title || feed.url));
+                html.appendChild(head);
+                html.appendChild(body);
+                doc.replaceChild(html, doc.documentElement);
+            } else {
+                doc.body.replaceWith(body);
+            }
+        }).catch(console.error);
+    })();
+"#;
+
+// Sekmede doğrudan açılan akış adresi ham XML yerine bu veriden okunabilir liste olarak çizilir
+// Yalnızca isteyen sekmenin kendi adresi çekilir; sayfa bu komutla başka adres okutamaz
+#[tauri::command]
+async fn feed_preview(webview: tauri::Webview, url: String) -> Result<feeds::Feed, String> {
+    let mut current = webview.url().map_err(|e| format!("Sekme adresi alınamadı: {}", e))?;
+    let mut requested = url::Url::parse(&url).map_err(|e| format!("Geçersiz URL: {}", e))?;
+    current.set_fragment(None);
+    requested.set_fragment(None);
+    if current != requested {
+        return Err("Akış önizlemesi yalnızca sekmenin kendi adresi için yapılabilir".to_string());
+    }
+    match http_get_body(&url).await? {
+        FetchedBody::Feed(bytes) => feeds::parse(&bytes, &url, FEED_PREVIEW_ENTRIES),
+        _ => Err("Adres bir RSS/Atom/JSON Feed akışı değil".to_string()),
+    }
+}
+
+// Sayfadaki veri tablolarını CSV ya da JSON olarak döndürür (chat panelinden dışa aktarma).
+#[tauri::command]
+async fn export_page_tables(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, url: String, format: String) -> Result<Vec<TableExport>, String> {
+    if !matches!(format.as_str(), "csv" | "json") {
+        return Err(format!("Bilinmeyen biçim: {} (csv ya da json olmalı)", format));
+    }
+    let html = page_html(&window, &state, &store, &url).await?;
+    let tables = tables::extract_all(&html);
+    if tables.is_empty() {
+        return Err("Sayfada veri tablosu bulunamadı".to_string());
+    }
+    tables
+        .iter()
+        .enumerate()
+        .map(|(i, t)| {
+            let data = if format == "csv" {
+                t.to_csv()
+            } else {
+                serde_json::to_string_pretty(&t.to_records()).map_err(|e| format!("JSON oluşturulamadı: {}", e))?
+            };
+            Ok(TableExport { index: i + 1, caption: t.caption.clone(), columns: t.headers.len(), rows: t.rows.len(), data })
+        })
+        .collect()
+}
+
+#[tauri::command]
+fn get_polite_config() -> Result<polite::PoliteConfig, String> {
+    Ok(polite::politeness().config())
+}
+
+#[tauri::command]
+fn set_polite_config(state: tauri::State<'_, ChatStore>, config: polite::PoliteConfig) -> Result<(), String> {
+    config.save(&state)?;
+    polite::politeness().configure(config);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_proxy_config() -> Result<proxy::ProxyConfig, String> {
+    Ok(proxy::current().masked())
+}
+
+// Yeni ayar sonraki isteklerde ve yeni açılan sekmelerde geçerli olur
+#[tauri::command]
+fn set_proxy_config(state: tauri::State<'_, ChatStore>, http: tauri::State<'_, http::Http>, config: proxy::ProxyConfig) -> Result<(), String> {
+    let config = config.with_stored_password(&proxy::current());
+    config.save(&state)?;
+    proxy::configure(config);
+    http.rebuild()
+}
+
+#[tauri::command]
+fn get_http_config(http: tauri::State<'_, http::Http>) -> Result<http::HttpConfig, String> {
+    Ok(http.config())
+}
+
+#[tauri::command]
+fn set_http_config(state: tauri::State<'_, ChatStore>, http: tauri::State<'_, http::Http>, config: http::HttpConfig) -> Result<(), String> {
+    config.save(&state)?;
+    http.configure(config)
+}
+
+// Ayar verilmezse kayıtlı vekil test edilir
+#[tauri::command]
+async fn test_proxy(config: Option<proxy::ProxyConfig>) -> Result<proxy::ProxyTestResult, String> {
+    let config = config.map(|c| c.with_stored_password(&proxy::current())).unwrap_or_else(proxy::current);
+    proxy::test(&config).await
+}
+
+#[tauri::command]
+fn get_strategy_chains(state: tauri::State<'_, ChatStore>) -> Result<Vec<strategy::DomainChain>, String> {
+    Ok(strategy::load_chains(&state))
+}
+
+#[tauri::command]
+fn set_strategy_chains(state: tauri::State<'_, ChatStore>, chains: Vec<strategy::DomainChain>) -> Result<(), String> {
+    strategy::save_chains(&state, &chains)
+}
+
+#[tauri::command]
+fn get_answer_language(state: tauri::State<'_, ChatStore>) -> Result<language::AnswerLanguage, String> {
+    Ok(language::AnswerLanguage::load(&state))
+}
+
+#[tauri::command]
+fn set_answer_language(state: tauri::State<'_, ChatStore>, config: language::AnswerLanguage) -> Result<(), String> {
+    config.save(&state)
+}
+
+#[tauri::command]
+fn get_cookie_sharing(state: tauri::State<'_, ChatStore>) -> Result<cookies::CookieSharing, String> {
+    Ok(cookies::CookieSharing::load(&state))
+}
+
+#[tauri::command]
+fn set_cookie_sharing(app: tauri::State<'_, AppState>, state: tauri::State<'_, ChatStore>, config: cookies::CookieSharing) -> Result<(), String> {
+    config.save(&state)?;
+    if let Ok(mut cache) = app.page_cache.lock() {
+        let purged = cache.purge_cookie_pages(|url| config.allows(url));
+        if purged > 0 { info!("Çerez paylaşımı kaldırılan sitelerin {} oturumlu sayfası önbellekten silindi", purged); }
     }
-    if out.is_empty() { None } else { Some(out) }
+    Ok(())
+}
+
+#[tauri::command]
+fn get_race_options(state: tauri::State<'_, ChatStore>) -> Result<strategy::RaceOptions, String> {
+    Ok(strategy::RaceOptions::load(&state))
+}
+
+#[tauri::command]
+fn set_race_options(state: tauri::State<'_, ChatStore>, options: strategy::RaceOptions) -> Result<(), String> {
+    options.save(&state)
 }
 
+// Yeni kalıp eklerken başlangıç noktası olarak varsayılan adımlar
+#[tauri::command]
+fn get_default_strategy_steps() -> Vec<strategy::StepConfig> {
+    strategy::default_steps()
+}
+
+#[tauri::command]
+fn get_crawl_options(state: tauri::State<'_, ChatStore>) -> Result<crawl::CrawlOptions, String> {
+    Ok(crawl::CrawlOptions::load(&state))
+}
+
+#[tauri::command]
+fn set_crawl_options(state: tauri::State<'_, ChatStore>, options: crawl::CrawlOptions) -> Result<(), String> {
+    options.save(&state)
+}
 
 // Ollama'ya soru sor - chat API ile sistem prompt desteği
 #[derive(Debug, Serialize, Deserialize)]
-struct OllamaChatMessage { role: String, content: String }
+struct OllamaChatMessage {
+    role: String,
+    content: String,
+    // Görsel destekli modeller (llava, llama3.2-vision...) için base64 görseller
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    images: Vec<String>,
+}
 
 #[derive(Debug, Serialize, Deserialize)]
 struct OllamaChatRequest { model: String, messages: Vec<OllamaChatMessage>, stream: bool }
@@ -1172,6 +1791,7 @@ struct OllamaChatRequest { model: String, messages: Vec<OllamaChatMessage>, stre
 #[derive(Debug, Serialize, Deserialize)]
 struct OllamaChatStreamChunk { model: Option<String>, message: Option<OllamaChatMessage>, response: Option<String>, done: Option<bool> }
 
+#[allow(clippy::too_many_arguments)]
 async fn query_ollama_with_content(
     window: tauri::Window,
     store: &tauri::State<'_, ChatStore>,
@@ -1179,34 +1799,38 @@ async fn query_ollama_with_content(
     question: String,
     model: String,
     history: Vec<(String, String)>,
+    images: Vec<String>,
+    language_directive: String,
 ) -> Result<String, String> {
-    let client = reqwest::Client::new();
+    let http = http::shared();
 
     // Aynı Instruction.md'yi kullanarak sistem prompt üret
     let system_prompt = read_instruction();
     let user_content = format!(
-        "Aşağıdaki web sayfası içeriğini analiz et ve sorulan soruya bu içeriğe dayanarak cevap ver:\n\n---\n\nWEB SAYFASI İÇERİĞİ (özetlenmiş):\n\n{}\n\n---\n\nSORU: {}\n\n---\n\nCevabı Türkçe ve kısa, net üret.",
-        &content[..content.len().min(8000)],
-        question
+        "Aşağıdaki web sayfası içeriğini analiz et ve sorulan soruya bu içeriğe dayanarak cevap ver:\n\n---\n\nWEB SAYFASI İÇERİĞİ (özetlenmiş):\n\n{}\n\n---\n\nSORU: {}\n\n---\n\n{}",
+        &content,
+        question,
+        language_directive
     );
 
     // Geçmişi role-based mesajlara çevir
     let mut messages: Vec<OllamaChatMessage> = Vec::new();
-    messages.push(OllamaChatMessage { role: "system".to_string(), content: system_prompt });
+    messages.push(OllamaChatMessage { role: "system".to_string(), content: system_prompt, images: Vec::new() });
     for (role, text) in history {
         let r = match role.as_str() {
             "assistant" => "assistant",
             "system" => "system",
             _ => "user",
         };
-        messages.push(OllamaChatMessage { role: r.to_string(), content: text });
+        messages.push(OllamaChatMessage { role: r.to_string(), content: text, images: Vec::new() });
     }
-    // Güncel kullanıcı mesajını en sona ekle
-    messages.push(OllamaChatMessage { role: "user".to_string(), content: user_content });
+    // Güncel kullanıcı mesajını (varsa görsellerle) en sona ekle
+    let has_images = !images.is_empty();
+    messages.push(OllamaChatMessage { role: "user".to_string(), content: user_content, images });
 
-    let request_body = OllamaChatRequest { model: model.to_string(), messages, stream: true };
+    let mut request_body = OllamaChatRequest { model: model.to_string(), messages, stream: true };
 
-    info!("Ollama chat (stream) çağrısı: model={}", model);
+    info!("Ollama chat (stream) çağrısı: model={} | görsel={}", model, has_images);
 
     fn default_base() -> String { "http://localhost:11434".to_string() }
     let base = store
@@ -1215,12 +1839,22 @@ async fn query_ollama_with_content(
         .unwrap_or_else(default_base);
     let chat_url = format!("{}/api/chat", base.trim_end_matches('/'));
 
-    let response = client
-        .post(&chat_url)
-        .json(&request_body)
-        .send()
+    let mut response = http
+        .send_streaming(http.post(&chat_url, http::Profile::Llm).json(&request_body))
         .await
         .map_err(|e| format!("Ollama'ya bağlanılamadı: {}", e))?;
+
+    // Model görsel desteklemiyorsa Ollama hata döner; görseller çıkarılıp tekrar denenir
+    if has_images && !response.status().is_success() {
+        let text = response.text().await.unwrap_or_default();
+        warn!("Ollama görselli isteği reddetti ({}) - görselsiz tekrar deneniyor.", text);
+        window.emit("vision-fallback", &serde_json::json!({"model": model})).ok();
+        if let Some(last) = request_body.messages.last_mut() { last.images.clear(); }
+        response = http
+            .send_streaming(http.post(&chat_url, http::Profile::Llm).json(&request_body))
+            .await
+            .map_err(|e| format!("Ollama'ya bağlanılamadı: {}", e))?;
+    }
     
     let mut stream = response.bytes_stream();
 
@@ -1228,8 +1862,8 @@ async fn query_ollama_with_content(
     let mut buffer = String::new();
     let mut final_text = String::new();
 
-    while let Some(chunk) = stream.next().await {
-        let chunk = chunk.map_err(|e| format!("Stream'den chunk okunamadı: {}", e))?;
+    // Okuma zaman aşımı: model uzun süre parça göndermezse akış kesilir
+    while let Some(chunk) = http.next_chunk(&mut stream).await? {
         let chunk_str = std::str::from_utf8(&chunk).map_err(|e| format!("Chunk UTF-8'e çevrilemedi: {}", e))?;
         
         buffer.push_str(chunk_str);
@@ -1317,9 +1951,28 @@ async fn query_ollama_with_content(
     Ok(final_text)
 }
 
-async fn query_openrouter_with_content(window: tauri::Window, content: String, question: String, model: String, system_prompt: String) -> Result<String, String> {
+// OpenRouter chat gövdesi; görsel varsa içerik metin + image_url parçalarından oluşan diziye dönüşür
+fn openrouter_chat_body(model: &str, text: &str, images: &[String]) -> serde_json::Value {
+    let content = if images.is_empty() {
+        serde_json::json!(text)
+    } else {
+        let mut parts = vec![serde_json::json!({"type": "text", "text": text})];
+        parts.extend(images.iter().map(|uri| serde_json::json!({"type": "image_url", "image_url": {"url": uri}})));
+        serde_json::Value::Array(parts)
+    };
+    serde_json::json!({
+        "model": model,
+        "stream": true,
+        "messages": [
+            {"role": "user", "content": content}
+        ]
+    })
+}
+
+// images: data URI listesi (data:image/png;base64,...)
+async fn query_openrouter_with_content(window: tauri::Window, content: String, question: String, model: String, system_prompt: String, images: Vec<String>, language_directive: String) -> Result<String, String> {
     let api_key = read_openrouter_api_key()?;
-    let client = reqwest::Client::new();
+    let http = http::shared();
 
     // Kaliteli free modeller (güncel OpenRouter listesi) - en güçlüler en üstte
     let preferred_free_models = vec![
@@ -1339,7 +1992,7 @@ async fn query_openrouter_with_content(window: tauri::Window, content: String, q
     }
     
     // Son olarak diğer ':free' modelleri ekle (nvidia vs.)
-    if let Ok(models) = get_openrouter_models().await {
+    if let Ok(models) = fetch_openrouter_models(http).await {
         for m in models {
             if m.name.ends_with(":free") && m.name != model && !preferred_free_models.contains(&m.name.as_str()) {
                 candidates.push(m.name);
@@ -1356,30 +2009,36 @@ async fn query_openrouter_with_content(window: tauri::Window, content: String, q
 
         // Mesaj içeriği (Gemini uyumlu tek 'user')
         let combined = format!(
-            "TALİMATLAR:\n{}\n\nWEB SAYFASI İÇERİĞİ (özetlenmiş):\n{}\n\nSORU:\n{}\n\nLütfen kısa ve net cevap ver.",
+            "TALİMATLAR:\n{}\n\nWEB SAYFASI İÇERİĞİ (özetlenmiş):\n{}\n\nSORU:\n{}\n\n{}",
             system_prompt,
-            &content[..content.len().min(8000)],
-            question
+            &content,
+            question,
+            language_directive
         );
-        let body = serde_json::json!({
-            "model": cand,
-            "stream": true,
-            "messages": [
-                {"role": "user", "content": combined}
-            ]
-        });
-
-        let response = client
-            .post("https://openrouter.ai/api/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", api_key))
-            .header("Content-Type", "application/json")
-            .header("Accept", "text/event-stream")
-            .header("HTTP-Referer", "http://localhost/")
-            .header("Referer", "http://localhost/")
-            .header("X-Title", "Nexus Browser")
-            .json(&body)
-            .send()
-            .await;
+        // Görsel desteklemeyen model isteği reddederse aynı model görselsiz tekrar denenir
+        let mut send_images = !images.is_empty();
+        let response = loop {
+            let body = openrouter_chat_body(cand, &combined, if send_images { &images } else { &[] });
+            let req = http
+                .post("https://openrouter.ai/api/v1/chat/completions", http::Profile::Llm)
+                .header("Authorization", format!("Bearer {}", api_key))
+                .header("Content-Type", "application/json")
+                .header("Accept", "text/event-stream")
+                .header("HTTP-Referer", "http://localhost/")
+                .header("Referer", "http://localhost/")
+                .header("X-Title", "Nexus Browser")
+                .json(&body);
+            let sent = http.send_streaming(req).await;
+            match sent {
+                Ok(r) if send_images && matches!(r.status().as_u16(), 400 | 404 | 415 | 422) => {
+                    let text = r.text().await.unwrap_or_default();
+                    warn!("OpenRouter modeli görselleri kabul etmedi ({}): {} - görselsiz tekrar deneniyor.", cand, text);
+                    window.emit("vision-fallback", &serde_json::json!({"model": cand})).ok();
+                    send_images = false;
+                }
+                other => break other,
+            }
+        };
 
         let response = match response {
             Ok(r) => r,
@@ -1402,8 +2061,7 @@ async fn query_openrouter_with_content(window: tauri::Window, content: String, q
         let mut buffer = String::new();
         let mut final_text = String::new();
 
-        while let Some(chunk) = stream.next().await {
-            let chunk = chunk.map_err(|e| format!("SSE chunk okunamadı: {}", e))?;
+        while let Some(chunk) = http.next_chunk(&mut stream).await? {
             let chunk_str = std::str::from_utf8(&chunk).map_err(|e| format!("Chunk UTF-8'e çevrilemedi: {}", e))?;
             buffer.push_str(chunk_str);
 
@@ -1442,21 +2100,17 @@ async fn query_openrouter_with_content(window: tauri::Window, content: String, q
             }
         }
 
-        // Stream bitti; içerik yoksa non-stream fallback dene
+        // Stream bitti; içerik yoksa non-stream fallback dene (aynı istem: cevap dili yönergesi dahil)
         if final_text.is_empty() {
-            let combined = format!(
-                "TALİMATLAR:\n{}\n\nWEB SAYFASI İÇERİĞİ (özetlenmiş):\n{}\n\nSORU:\n{}\n\nLütfen kısa ve net cevap ver.",
-                system_prompt,
-                &content[..content.len().min(8000)],
-                question
-            );
             let fallback_body = serde_json::json!({
                 "model": cand,
                 "stream": false,
                 "messages": [ {"role": "user", "content": combined} ]
             });
-            let resp = client
-                .post("https://openrouter.ai/api/v1/chat/completions")
+            // Akışsız istek: tüm cevap ilk yanıt süresi içinde gelmeli
+            let resp = http
+                .post("https://openrouter.ai/api/v1/chat/completions", http::Profile::Llm)
+                .timeout(http.config().first_byte())
                 .header("Authorization", format!("Bearer {}", api_key))
                 .header("Content-Type", "application/json")
                 .header("HTTP-Referer", "http://localhost/")
@@ -1494,7 +2148,8 @@ async fn query_openrouter_with_content(window: tauri::Window, content: String, q
 
 // Ana soru sorma komutu
 #[tauri::command]
-async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, logger: tauri::State<'_, RedisLogger>, mut url: String, question: String, model: String) -> Result<(), String> {
+#[allow(clippy::too_many_arguments)]
+async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, logger: tauri::State<'_, RedisLogger>, mut url: String, question: String, model: String, scope: Option<String>, include_images: Option<bool>) -> Result<(), String> {
     // Her zaman aktif sekmenin güncel URL'ini prefer et
     let original_url = url.clone();
     let mut effective_url: Option<String> = None;
@@ -1508,50 +2163,26 @@ async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>,
     if let Some(u) = effective_url { url = u; }
     info!("'ask_question' komutu başlatıldı. URL: {} (orijinal: {})", url, original_url);
 
-    // Cache kontrolü
-    let ttl = Duration::from_secs(300); // 5 dakika TTL
-    let mut use_cached = false;
-    let mut cached_content: Option<String> = None;
-    let mut cached_source: Option<String> = None;
-    {
-        if let Ok(cache) = state.page_cache.lock() {
-            if let Some(entry) = cache.get(&url) {
-                if entry.fetched_at.elapsed() < ttl {
-                    info!("Cache hit: URL içeriği TTL içinde. Yeniden scrape edilmeyecek.");
-                    use_cached = true;
-                    cached_content = Some(entry.content.clone());
-                    cached_source = Some(entry.source.clone());
-                } else {
-                    info!("Cache expired: URL içeriği süresi dolmuş. Yeniden scrape edilecek.");
-                }
-            }
-        }
-    }
+    // "Neler yeni?" türü sorularda kapsam verilmemişse sayfanın akışı kullanılır
+    let scope = scope.or_else(|| feeds::is_whats_new_question(&question).then(|| "feed".to_string()));
 
-    // Adım 1: Sayfayı scrape et (veya cache)
-    let (content, source_label, from_cache) = if use_cached {
-        (
-            cached_content.unwrap_or_default(),
-            cached_source.unwrap_or_else(|| "cache_unknown".to_string()),
-            true,
-        )
+    // Adım 1: Sayfayı (ya da site derlemini) al
+    let (page, from_cache) = acquire_page(&window, &state, &store, &url, scope.as_deref()).await?;
+    let content = page.content.clone();
+    let source_label = page.source.clone();
+    // Sayfa dili ve ayara göre cevap dili
+    let page_language = language::detect(&content);
+    let answer_language = language::AnswerLanguage::load(&store).target(&question, page_language.as_ref());
+
+    // Soru için istenmişse sayfa görselleri (site kapsamında tek sayfa olmadığı için alınmaz)
+    let page_images = if include_images.unwrap_or(false) && scope.as_deref() != Some("site") {
+        page_images(&page).await
     } else {
-        let (fresh, source) = scrape_page_content(url.clone()).await?;
-        // Cache'e yaz ve basit boyut limiti uygula
-        if let Ok(mut cache) = state.page_cache.lock() {
-            if cache.len() > 16 {
-                if let Some(first_key) = cache.keys().next().cloned() {
-                    cache.remove(&first_key);
-                }
-            }
-            cache.insert(url.clone(), CachedPage { content: fresh.clone(), source: source.clone(), fetched_at: Instant::now() });
-        }
-        (fresh, source, false)
+        Vec::new()
     };
 
     // Detaylı log + frontend'e bilgi gönderimi
-    let preview_len = content.len().min(2000);
-    let preview = &content[..preview_len];
+    let preview = readability::truncate_utf8(&content, 2000);
     info!(
         "MODEL KAYNAK ÖZETI | mode=ollama | url={} | source={} | from_cache={} | length={} | preview='{}'",
         url, source_label, from_cache, content.len(), preview.replace('\n', " ")
@@ -1562,8 +2193,17 @@ async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>,
             "mode": "ollama",
             "url": url,
             "source": source_label,
+            "encoding": page.encoding,
             "from_cache": from_cache,
             "length": content.len(),
+            "attempts": page.attempts,
+            "links": page.links.len(),
+            "cookies": page.cookies,
+            "cleanup": page.cleanup,
+            "metadata": page.metadata,
+            "language": page_language,
+            "answer_language": answer_language,
+            "images": page_images,
             "preview": preview
         })
     ).ok();
@@ -1579,6 +2219,7 @@ async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>,
         "mode": "ollama",
         "url": url,
         "source": source_label,
+        "encoding": page.encoding,
         "from_cache": from_cache,
         "content_length": content.len(),
         "content_key": content_key,
@@ -1590,7 +2231,10 @@ async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>,
     let session_id = store.upsert_session(&url)?;
     let history_pairs = store.get_messages(session_id, 10)?; // (role, content)
     // Adım 2: Ollama'ya sor (stream olarak) ve nihai cevabı al
-    let assistant_text = query_ollama_with_content(window.clone(), &store, content.clone(), question.clone(), model.clone(), history_pairs).await?;
+    let context = page_context(&page);
+    let image_data = page_images.iter().map(|i| i.base64.clone()).collect();
+    let assistant_text = query_ollama_with_content(window.clone(), &store, context, question.clone(), model.clone(), history_pairs, image_data, language::directive(answer_language)).await?;
+    let assistant_text = resolve_answer_links(&window, &page, assistant_text);
 
     // Mesajları DB'ye kaydet
     store.add_message(session_id, "user", &question)?;
@@ -1601,7 +2245,7 @@ async fn ask_question(window: tauri::Window, state: tauri::State<'_, AppState>,
         "mode": "ollama",
         "url": url,
         "model": model,
-        "answer_preview": &assistant_text[..assistant_text.len().min(1000)],
+        "answer_preview": readability::truncate_utf8(&assistant_text, 1000),
         "content_source": source_label
     }));
 
@@ -1619,6 +2263,7 @@ Nexus Browser adlı bir tarayıcıda çalışan bir Yapay Zeka Web Tarayıcısı
 - Her zaman önce sana sağlanan sayfa içeriğini referans al.
 - Kaynak sayfadan alıntı yaparken bilgileri özetle ve açık, net ifadeler kullan.
 - Bilinmeyen konularda varsayımda bulunma, "bilmiyorum" demekten çekinme.
+- Bağlantı verirken adres uydurma; içeriğin sonundaki "Bağlantılar" listesinden [L3] gibi numarasıyla atıf yap.
 - Cevaplarını, sana sunulan metne sadık kalarak detaylı, bilgilendirici ve kapsamlı bir şekilde oluştur.
 
 İşlem sırasında aşağıdaki ilkelere uy:
@@ -1629,14 +2274,15 @@ Kullanıcı chat alanına aşağıdaki gibi komutlar yazarsa, cevap stilini ona
 - /ozetle — Websitesi verilerini kısa özetle
 - /acikla — Websitesi verilerini detaylı açıkla
 - /madde — Websitesi verilerini maddeler halinde yaz
-- /kaynakekle — Websitesindeki kaynakları belirt
+- /kaynakekle — Websitesindeki kaynakları "Bağlantılar" listesindeki numaralarıyla ([L3]) belirt
 - /kisalt — Önceki cevabını daha kısa yaz
 - /uzat — Önceki cevabını daha detaylı yaz
 "#.to_string()
 }
 
 #[tauri::command]
-async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, logger: tauri::State<'_, RedisLogger>, mut url: String, question: String, model: String) -> Result<(), String> {
+#[allow(clippy::too_many_arguments)]
+async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, logger: tauri::State<'_, RedisLogger>, mut url: String, question: String, model: String, scope: Option<String>, include_images: Option<bool>) -> Result<(), String> {
     let original_url = url.clone();
     let mut effective_url: Option<String> = None;
     if let Ok(last) = state.last_active_tab.lock() {
@@ -1648,42 +2294,23 @@ async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_,
     }
     if let Some(u) = effective_url { url = u; }
     info!("'ask_question_openrouter' komutu başlatıldı. URL: {} (orijinal: {}) | model: {}", url, original_url, model);
-    // Cache/scrape aynı mantık
-    let ttl = Duration::from_secs(300);
-    let mut use_cached = false;
-    let mut cached_content: Option<String> = None;
-    let mut cached_source: Option<String> = None;
-    {
-        if let Ok(cache) = state.page_cache.lock() {
-            if let Some(entry) = cache.get(&url) {
-                if entry.fetched_at.elapsed() < ttl {
-                    use_cached = true;
-                    cached_content = Some(entry.content.clone());
-                    cached_source = Some(entry.source.clone());
-                }
-            }
-        }
-    }
-    let (content, source_label, from_cache) = if use_cached {
-        (
-            cached_content.unwrap_or_default(),
-            cached_source.unwrap_or_else(|| "cache_unknown".to_string()),
-            true,
-        )
+    let scope = scope.or_else(|| feeds::is_whats_new_question(&question).then(|| "feed".to_string()));
+    let (page, from_cache) = acquire_page(&window, &state, &store, &url, scope.as_deref()).await?;
+    let content = page.content.clone();
+    let source_label = page.source.clone();
+    // Sayfa dili ve ayara göre cevap dili
+    let page_language = language::detect(&content);
+    let answer_language = language::AnswerLanguage::load(&store).target(&question, page_language.as_ref());
+
+    // Soru için istenmişse sayfa görselleri (site kapsamında tek sayfa olmadığı için alınmaz)
+    let page_images = if include_images.unwrap_or(false) && scope.as_deref() != Some("site") {
+        page_images(&page).await
     } else {
-        let (fresh, source) = scrape_page_content(url.clone()).await?;
-        if let Ok(mut cache) = state.page_cache.lock() {
-            if cache.len() > 16 {
-                if let Some(first_key) = cache.keys().next().cloned() { cache.remove(&first_key); }
-            }
-            cache.insert(url.clone(), CachedPage { content: fresh.clone(), source: source.clone(), fetched_at: Instant::now() });
-        }
-        (fresh, source, false)
+        Vec::new()
     };
 
     // Detaylı log + frontend'e bilgi gönderimi
-    let preview_len = content.len().min(2000);
-    let preview = &content[..preview_len];
+    let preview = readability::truncate_utf8(&content, 2000);
     info!(
         "MODEL KAYNAK ÖZETI | mode=openrouter | url={} | source={} | from_cache={} | length={} | preview='{}'",
         url, source_label, from_cache, content.len(), preview.replace('\n', " ")
@@ -1694,8 +2321,17 @@ async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_,
             "mode": "openrouter",
             "url": url,
             "source": source_label,
+            "encoding": page.encoding,
             "from_cache": from_cache,
             "length": content.len(),
+            "attempts": page.attempts,
+            "links": page.links.len(),
+            "cookies": page.cookies,
+            "cleanup": page.cleanup,
+            "metadata": page.metadata,
+            "language": page_language,
+            "answer_language": answer_language,
+            "images": page_images,
             "preview": preview
         })
     ).ok();
@@ -1711,6 +2347,7 @@ async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_,
         "mode": "openrouter",
         "url": url,
         "source": source_label,
+        "encoding": page.encoding,
         "from_cache": from_cache,
         "content_length": content.len(),
         "content_key": content_key,
@@ -1719,7 +2356,10 @@ async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_,
     }));
 
     let system_prompt = read_instruction();
-    let assistant_text = query_openrouter_with_content(window.clone(), content.clone(), question.clone(), model.clone(), system_prompt).await?;
+    let context = page_context(&page);
+    let image_data = page_images.iter().map(images::PageImage::data_uri).collect();
+    let assistant_text = query_openrouter_with_content(window.clone(), context, question.clone(), model.clone(), system_prompt, image_data, language::directive(answer_language)).await?;
+    let assistant_text = resolve_answer_links(&window, &page, assistant_text);
 
     // Mesajları DB'ye kaydet (Ollama ile aynı mantık)
     let session_id = store.upsert_session(&url)?;
@@ -1731,7 +2371,7 @@ async fn ask_question_openrouter(window: tauri::Window, state: tauri::State<'_,
         "mode": "openrouter",
         "url": url,
         "model": model,
-        "answer_preview": &assistant_text[..assistant_text.len().min(1000)],
+        "answer_preview": readability::truncate_utf8(&assistant_text, 1000),
         "content_source": source_label
     }));
     Ok(())
@@ -1756,6 +2396,16 @@ fn set_ollama_base_url(state: tauri::State<'_, ChatStore>, value: String) -> Res
     Ok(())
 }
 
+#[tauri::command]
+fn get_firecrawl_config(state: tauri::State<'_, ChatStore>) -> Result<firecrawl::FirecrawlConfig, String> {
+    Ok(firecrawl::FirecrawlConfig::load(&state).masked())
+}
+
+#[tauri::command]
+fn set_firecrawl_config(state: tauri::State<'_, ChatStore>, config: firecrawl::FirecrawlConfig) -> Result<(), String> {
+    config.save(&state)
+}
+
 #[tauri::command]
 async fn reposition_webview(window: tauri::Window, tab_id: String, x: f64, y: f64, width: f64, height: f64) -> Result<(), String> {
     if let Some(webview) = window.get_webview(&tab_id) {
@@ -1869,7 +2519,11 @@ async fn open_or_navigate_browser_tab(window: tauri::Window, state: tauri::State
 
     // 3) Yeni webview oluştur
     let parsed = tauri::Url::parse(&url).map_err(|e| format!("URL parse hatası: {}", e))?;
-    let builder = WebviewBuilder::new(&tab_id, tauri::WebviewUrl::External(parsed));
+    let mut builder = WebviewBuilder::new(&tab_id, tauri::WebviewUrl::External(parsed));
+    // Vekil webview oluşturulurken verilir; ayar değişikliği yalnızca yeni sekmelere yansır
+    if let Some(proxy_url) = proxy::webview_proxy_url() {
+        builder = builder.proxy_url(proxy_url);
+    }
     match window.add_child(
         builder,
         tauri::LogicalPosition::new(0.0, 0.0),
@@ -2065,20 +2719,90 @@ async fn get_page_info(window: tauri::Window, tab_id: String, url: String) -> Re
 
 #[tauri::command]
 async fn clear_cache_for_url(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, url: String) -> Result<(), String> {
-    // Bellek cache'ini temizle
+    // Bellek ve kalıcı sayfa cache'ini temizle
     if let Ok(mut cache) = state.page_cache.lock() {
         cache.remove(&url);
     }
+    store.cache_delete(&url)?;
     // DB oturum ve mesajlarını temizle
     store.clear_for_url(&url)?;
     Ok(())
 }
 
+#[tauri::command]
+fn get_page_cache_config(store: tauri::State<'_, ChatStore>) -> Result<page_cache::CacheConfig, String> {
+    Ok(page_cache::CacheConfig::load(&store))
+}
+
+// Sınırlar küçüldüyse bellekteki fazla kayıtlar hemen atılır
+#[tauri::command]
+fn set_page_cache_config(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, config: page_cache::CacheConfig) -> Result<(), String> {
+    config.save(&store)?;
+    state.page_cache.lock().map_err(|_| "Önbellek kilidi".to_string())?.configure(config);
+    prune_stored_cache(&state, &store);
+    Ok(())
+}
+
+#[tauri::command]
+fn get_page_cache_stats(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>) -> Result<page_cache::CacheStats, String> {
+    let mut stats = state.page_cache.lock().map_err(|_| "Önbellek kilidi".to_string())?.stats();
+    (stats.stored_entries, stats.stored_bytes) = store.cache_totals()?;
+    Ok(stats)
+}
+
+// Kalıcı tablodaki ve yalnızca bellekte olan kayıtlar, en yeni önce; bellekte olanlar işaretlenir
+#[tauri::command]
+fn list_page_cache(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>) -> Result<Vec<page_cache::EntryInfo>, String> {
+    let mut entries = store.cache_list()?;
+    let cache = state.page_cache.lock().map_err(|_| "Önbellek kilidi".to_string())?;
+    for entry in &mut entries {
+        entry.in_memory = cache.contains(&entry.url);
+    }
+    // Yalnızca bellekte olan kayıtlar (sekme çerezleriyle çekilmiş sayfalar diske yazılmaz)
+    let stored: HashSet<String> = entries.iter().map(|e| e.url.clone()).collect();
+    entries.extend(cache.list().into_iter().filter(|e| !stored.contains(&e.url)));
+    entries.sort_by_key(|e| std::cmp::Reverse(e.fetched_at));
+    Ok(entries)
+}
+
+// Alan adı ve alt alan adlarının kayıtları; silinen kalıcı kayıt sayısı döner
+#[tauri::command]
+fn purge_page_cache_domain(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>, domain: String) -> Result<usize, String> {
+    if domain.trim().is_empty() {
+        return Err("Alan adı boş olamaz".to_string());
+    }
+    let memory = state.page_cache.lock().map_err(|_| "Önbellek kilidi".to_string())?.purge_domain(&domain);
+    let stored = store.cache_purge_domain(&domain)?;
+    info!("Önbellek temizlendi ({}): bellek {}, kalıcı {} kayıt", domain, memory, stored);
+    Ok(stored.max(memory))
+}
+
+#[tauri::command]
+fn purge_page_cache(state: tauri::State<'_, AppState>, store: tauri::State<'_, ChatStore>) -> Result<usize, String> {
+    let memory = state.page_cache.lock().map_err(|_| "Önbellek kilidi".to_string())?.clear();
+    let stored = store.cache_clear()?;
+    info!("Önbellek temizlendi: bellek {}, kalıcı {} kayıt", memory, stored);
+    Ok(stored.max(memory))
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Chat verilerini kalıcı tutmamak için açılışta temizle
     let store = ChatStore::new("chat.db").expect("chat db başlatılamadı");
     let _ = store.clear_all();
+    // Eski genel "canlı DOM" ayarı zincirdeki live_dom adımına taşınır
+    if let Err(e) = strategy::migrate_legacy_setting(&store) { warn!("{}", e); }
+    // Sayfa önbelleği: bellek katmanı ve kalıcı tablo aynı sınırlarla başlar
+    let cache_config = page_cache::CacheConfig::load(&store);
+    let _ = store.cache_prune(&cache_config);
+    let state = AppState { page_cache: Mutex::new(page_cache::PageCache::new(cache_config)), ..Default::default() };
+    // Arka plan isteklerinin user-agent / robots / hız ayarları
+    polite::politeness().configure(polite::PoliteConfig::load(&store));
+    // Tüm dış istekler ve yeni sekmeler için vekil sunucu
+    proxy::configure(proxy::ProxyConfig::load(&store));
+    // Tek, havuzlu HTTP istemcisi (vekil ayarından sonra kurulur)
+    let http = http::Http::new(http::HttpConfig::load(&store)).expect("HTTP istemcisi başlatılamadı");
+    http::install(http.clone());
 
     // Redis logger'ı sabit URL ile başlat (TLS - rediss)
     let redis_logger = RedisLogger::with_url(
@@ -2086,9 +2810,10 @@ pub fn run() {
     );
 
     tauri::Builder::default()
-        .manage(AppState::default())
+        .manage(state)
         .manage(store)
         .manage(redis_logger)
+        .manage(http)
         .invoke_handler(tauri::generate_handler![
             get_ollama_models,
             get_openrouter_models,
@@ -2113,7 +2838,37 @@ pub fn run() {
             get_ollama_base_url,
             set_ollama_base_url,
             get_page_info,
-            notify_url_change
+            notify_url_change,
+            submit_live_dom,
+            get_firecrawl_config,
+            set_firecrawl_config,
+            crawl_site,
+            get_crawl_options,
+            get_strategy_chains,
+            set_strategy_chains,
+            get_default_strategy_steps,
+            get_race_options,
+            set_race_options,
+            get_polite_config,
+            export_page_tables,
+            feed_preview,
+            set_polite_config,
+            get_proxy_config,
+            set_proxy_config,
+            test_proxy,
+            get_http_config,
+            set_http_config,
+            get_page_cache_config,
+            set_page_cache_config,
+            get_page_cache_stats,
+            list_page_cache,
+            purge_page_cache_domain,
+            purge_page_cache,
+            get_cookie_sharing,
+            set_cookie_sharing,
+            get_answer_language,
+            set_answer_language,
+            set_crawl_options
         ])
         .on_page_load(|window, payload| {
             let tab_id = window.label().to_string();
@@ -2144,6 +2899,8 @@ pub fn run() {
                 }})();
             "#, url, tab_id);
             let _ = window.eval(&monitor_js);
+            // Doğrudan açılan RSS/Atom/JSON Feed belgesi okunabilir listeye çevrilir
+            let _ = window.eval(FEED_VIEW_JS);
         })
         .setup(|app| {
             // Devtools otomatik açma kaldırıldı. Option+Cmd+I ile aç/kapat.
@@ -0,0 +1,222 @@
+// Sayfadaki dış bağlantılar.
+// Bağlantılar <base href> / sayfa adresine göre çözülür, parça (#...) atılarak tekilleştirilir ve
+// iç / dış / menü / alt bilgi olarak sınıflanır. Model bağlamına numaralı bir "Bağlantılar" eki
+// eklenir; cevapta geçen [L3] gibi atıflar backend'de gerçek adreslere çevrilir.
+
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::readability;
+
+// Ekte yer alacak en fazla bağlantı sayısı ve ekin bayt sınırı
+const MAX_LINKS: usize = 60;
+const MAX_APPENDIX_BYTES: usize = 3000;
+const MAX_TEXT_CHARS: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkKind {
+    Internal,
+    External,
+    Nav,
+    Footer,
+}
+
+impl LinkKind {
+    fn label(self) -> &'static str {
+        match self {
+            LinkKind::Internal => "iç",
+            LinkKind::External => "dış",
+            LinkKind::Nav => "menü",
+            LinkKind::Footer => "alt bilgi",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Link {
+    pub n: usize,
+    pub url: String,
+    pub text: String,
+    pub kind: LinkKind,
+}
+
+// HTML'deki <a href> bağlantıları. İçerikteki bağlantılar önce, menü ve alt bilgi bağlantıları sonra gelir.
+pub fn from_html(html: &str, page_url: &str) -> Vec<Link> {
+    let Ok(page) = url::Url::parse(page_url) else { return Vec::new() };
+    let doc = Html::parse_document(html);
+    let attr = |sel: &str, name: &str| -> Option<String> {
+        let sel = Selector::parse(sel).ok()?;
+        doc.select(&sel).next().and_then(|e| e.value().attr(name)).map(|v| v.trim().to_string())
+    };
+    // Göreli adresler <base href> varsa ona, yoksa sayfa adresine göre çözülür
+    let base = attr("base[href]", "href").and_then(|h| page.join(&h).ok()).unwrap_or_else(|| page.clone());
+    let canonical = attr("link[rel='canonical'][href]", "href").and_then(|h| page.join(&h).ok());
+    let own_hosts: Vec<String> = [Some(&page), canonical.as_ref()].into_iter().flatten().filter_map(host_key).collect();
+    let own_pages: Vec<String> = [Some(&page), canonical.as_ref()].into_iter().flatten().map(dedupe_key).collect();
+
+    let Ok(sel) = Selector::parse("a[href]") else { return Vec::new() };
+    let mut links: Vec<Link> = Vec::new();
+    let mut seen = HashMap::new();
+    for a in doc.select(&sel) {
+        let href = a.value().attr("href").unwrap_or("").trim();
+        let Some(u) = resolve(&base, href) else { continue };
+        if own_pages.contains(&dedupe_key(&u)) { continue; }
+        let text = link_text(a);
+        let kind = match region(a) {
+            Some(kind) => kind,
+            None if host_key(&u).map(|h| own_hosts.contains(&h)).unwrap_or(false) => LinkKind::Internal,
+            None => LinkKind::External,
+        };
+        push(&mut links, &mut seen, u, text, kind);
+    }
+    finish(links)
+}
+
+// HTML'i olmayan kaynaklar (Firecrawl Markdown'ı, akış listesi) için [metin](adres) ve çıplak adresler
+pub fn from_markdown(markdown: &str, page_url: &str) -> Vec<Link> {
+    let Ok(page) = url::Url::parse(page_url) else { return Vec::new() };
+    let own_host = host_key(&page);
+    let own_page = dedupe_key(&page);
+    let mut links: Vec<Link> = Vec::new();
+    let mut seen = HashMap::new();
+    let mut rest = markdown;
+    while let Some(idx) = rest.find("http") {
+        let before = &rest[..idx];
+        let tail = &rest[idx..];
+        let end = tail.find(|c: char| c.is_whitespace() || matches!(c, ')' | '>' | '<' | '"' | ']')).unwrap_or(tail.len());
+        let raw = tail[..end].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        rest = &tail[end..];
+        // [metin](adres) biçiminde etiket; görsel bağlantıları (![alt](src)) atlanır
+        let label_start = before.strip_suffix("](").and_then(|b| b.rfind('['));
+        if label_start.map(|p| before[..p].ends_with('!')).unwrap_or(false) { continue; }
+        let Ok(u) = url::Url::parse(raw) else { continue };
+        if !matches!(u.scheme(), "http" | "https") || dedupe_key(&u) == own_page { continue; }
+        let text = label_start.map(|p| before[p + 1..before.len() - 2].to_string()).unwrap_or_default();
+        let kind = if host_key(&u) == own_host { LinkKind::Internal } else { LinkKind::External };
+        push(&mut links, &mut seen, u, readability::collapse_ws(&text), kind);
+    }
+    finish(links)
+}
+
+fn resolve(base: &url::Url, href: &str) -> Option<url::Url> {
+    if href.is_empty() || href.starts_with('#') { return None; }
+    let mut u = base.join(href).ok()?;
+    if !matches!(u.scheme(), "http" | "https") { return None; }
+    u.set_fragment(None);
+    Some(u)
+}
+
+// Aynı sayfanın www'lu/www'suz, sonda / olan/olmayan biçimleri tek bağlantı sayılır
+fn dedupe_key(u: &url::Url) -> String {
+    let host = host_key(u).unwrap_or_default();
+    let path = u.path().trim_end_matches('/');
+    match u.query() {
+        Some(q) => format!("{}{}?{}", host, path, q),
+        None => format!("{}{}", host, path),
+    }
+}
+
+fn host_key(u: &url::Url) -> Option<String> {
+    u.host_str().map(|h| h.trim_start_matches("www.").to_lowercase())
+}
+
+// Bağlantı menü ya da alt bilgi bölgesinde mi?
+fn region(a: ElementRef<'_>) -> Option<LinkKind> {
+    for anc in a.ancestors().filter_map(ElementRef::wrap) {
+        let v = anc.value();
+        match (v.name(), v.attr("role")) {
+            ("nav", _) | (_, Some("navigation")) | (_, Some("menubar")) => return Some(LinkKind::Nav),
+            ("footer", _) | (_, Some("contentinfo")) => return Some(LinkKind::Footer),
+            _ => {}
+        }
+        // Sınıf/id ipuçları (div.menu, #footer vb.)
+        let hints = format!("{} {}", v.attr("class").unwrap_or(""), v.attr("id").unwrap_or("")).to_lowercase();
+        if hints.contains("footer") { return Some(LinkKind::Footer); }
+        if hints.split(|c: char| !c.is_alphanumeric()).any(|w| matches!(w, "nav" | "navbar" | "menu" | "breadcrumb" | "breadcrumbs")) {
+            return Some(LinkKind::Nav);
+        }
+    }
+    None
+}
+
+fn link_text(a: ElementRef<'_>) -> String {
+    let text = readability::collapse_ws(&a.text().collect::<String>());
+    let text = if text.is_empty() {
+        // Görsel bağlantılar için alt / title / aria-label
+        let img_alt = Selector::parse("img[alt]").ok().and_then(|s| a.select(&s).next()).and_then(|i| i.value().attr("alt").map(str::to_string));
+        a.value().attr("aria-label").or(a.value().attr("title")).map(str::to_string).or(img_alt).unwrap_or_default()
+    } else {
+        text
+    };
+    readability::truncate_chars(&readability::collapse_ws(&text), MAX_TEXT_CHARS)
+}
+
+// Aynı adres birden çok kez geçerse ilk boş olmayan metin ve en "içerik" sınıfı korunur.
+// `seen`: dedupe_key -> links içindeki sıra (binlerce bağlantılı sayfalarda doğrusal arama yapılmaz)
+fn push(links: &mut Vec<Link>, seen: &mut HashMap<String, usize>, u: url::Url, text: String, kind: LinkKind) {
+    let key = dedupe_key(&u);
+    if let Some(&i) = seen.get(&key) {
+        let existing = &mut links[i];
+        if existing.text.is_empty() { existing.text = text; }
+        existing.kind = existing.kind.min(kind);
+        return;
+    }
+    seen.insert(key, links.len());
+    links.push(Link { n: 0, url: u.to_string(), text, kind });
+}
+
+fn finish(mut links: Vec<Link>) -> Vec<Link> {
+    // Kararlı sıralama: sınıf içinde belge sırası korunur
+    links.sort_by_key(|l| matches!(l.kind, LinkKind::Nav | LinkKind::Footer));
+    links.truncate(MAX_LINKS);
+    for (i, l) in links.iter_mut().enumerate() { l.n = i + 1; }
+    links
+}
+
+// Model bağlamına eklenecek numaralı liste; bayt sınırını aşan bağlantılar eklenmez
+pub fn render_appendix(links: &[Link]) -> String {
+    if links.is_empty() { return String::new(); }
+    let mut out = String::from("Bağlantılar (cevapta [L3] gibi numarayla atıf yap):\n");
+    for l in links {
+        let line = if l.text.is_empty() {
+            format!("[L{}] {} ({})\n", l.n, l.url, l.kind.label())
+        } else {
+            format!("[L{}] {} — {} ({})\n", l.n, l.text, l.url, l.kind.label())
+        };
+        if out.len() + line.len() > MAX_APPENDIX_BYTES { break; }
+        out.push_str(&line);
+    }
+    out
+}
+
+// Cevaptaki [L3] atıflarını Markdown bağlantısına çevirir: "[L3](https://...)".
+// Zaten bağlantı olan ([L3](...)) ya da listede olmayan numaralar olduğu gibi kalır. (yeni metin, atıf yapılan bağlantılar)
+pub fn resolve_refs(answer: &str, links: &[Link]) -> (String, Vec<Link>) {
+    let mut out = String::with_capacity(answer.len());
+    let mut cited: Vec<Link> = Vec::new();
+    let mut rest = answer;
+    while let Some(idx) = rest.find("[L") {
+        out.push_str(&rest[..idx]);
+        let tail = &rest[idx..];
+        let digits: String = tail[2..].chars().take_while(|c| c.is_ascii_digit()).collect();
+        let close = 2 + digits.len();
+        let is_ref = !digits.is_empty() && tail[close..].starts_with(']') && !tail[close + 1..].starts_with('(');
+        let link = digits.parse::<usize>().ok().and_then(|n| links.iter().find(|l| l.n == n));
+        match (is_ref, link) {
+            (true, Some(link)) => {
+                out.push_str(&format!("[L{}]({})", link.n, link.url));
+                if !cited.iter().any(|c| c.n == link.n) { cited.push(link.clone()); }
+                rest = &tail[close + 1..];
+            }
+            _ => {
+                out.push_str("[L");
+                rest = &tail[2..];
+            }
+        }
+    }
+    out.push_str(rest);
+    (out, cited)
+}
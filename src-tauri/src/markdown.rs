@@ -0,0 +1,332 @@
+// Yapıyı koruyan HTML -> Markdown dönüştürücü.
+// Başlıklar, listeler, bağlantılar, GFM tabloları ve dil ipuçlu kod blokları
+// korunur; böylece yerel çıkarım çıktısı Firecrawl'ın `markdown` alanına yakın olur.
+
+use scraper::{ElementRef, Html, Node, Selector};
+use url::Url;
+
+use crate::{readability, tables};
+
+// Markdown'a hiç yansımayacak etiketler
+const SKIP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "svg", "canvas", "head", "button",
+    "input", "select", "textarea", "object", "embed",
+];
+
+const BLOCK_TAGS: &[&str] = &[
+    "p", "div", "section", "article", "main", "header", "footer", "nav", "aside", "figure",
+    "figcaption", "address", "details", "summary", "center", "form", "fieldset", "dl",
+    // Yerleşim tablolarının hücreleri (veri tabloları render_table'da GFM'e çevrilir)
+    "tr", "td", "th", "caption",
+];
+
+// Dönüşüm bağlamı: göreli URL'ler için taban adres ve içerik dışı blokların budanması
+pub struct Ctx<'u> {
+    pub base: Option<&'u Url>,
+    // true ise nav/footer/sidebar gibi readability'nin elediği alt düğümler atlanır
+    pub prune: bool,
+}
+
+// Tüm sayfa gövdesini Markdown'a çevirir.
+pub fn html_to_markdown(html: &str, base_url: Option<&str>) -> String {
+    let doc = Html::parse_document(html);
+    let base = base_url.and_then(|u| Url::parse(u).ok());
+    let body = Selector::parse("body").ok().and_then(|s| doc.select(&s).next());
+    let root = body.unwrap_or_else(|| doc.root_element());
+    element_to_markdown(root, &Ctx { base: base.as_ref(), prune: false })
+}
+
+// Readability ile seçilen ana içerik düğümünü Markdown'a çevirir.
+pub fn main_content_markdown(html: &str, base_url: Option<&str>) -> String {
+    let doc = Html::parse_document(html);
+    let base = base_url.and_then(|u| Url::parse(u).ok());
+    match readability::find_main_content(&doc) {
+        Some(el) => element_to_markdown(el, &Ctx { base: base.as_ref(), prune: true }),
+        None => String::new(),
+    }
+}
+
+pub fn element_to_markdown(el: ElementRef<'_>, cx: &Ctx<'_>) -> String {
+    let mut out = String::new();
+    render_children(el, cx, &mut out);
+    normalize(&out)
+}
+
+fn render_children(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String) {
+    for child in el.children() {
+        match child.value() {
+            Node::Text(t) => push_text(out, t),
+            Node::Element(_) => {
+                if let Some(c) = ElementRef::wrap(child) { render_element(c, cx, out); }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn render_element(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String) {
+    let v = el.value();
+    let name = v.name();
+    if SKIP_TAGS.contains(&name) || v.attr("hidden").is_some() || v.attr("aria-hidden") == Some("true") {
+        return;
+    }
+    if cx.prune && readability::is_unlikely(el) { return; }
+    match name {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse::<usize>().unwrap_or(1);
+            let text = inline(el, cx);
+            if text.is_empty() { return; }
+            block_start(out);
+            out.push_str(&"#".repeat(level));
+            out.push(' ');
+            out.push_str(&text);
+            block_end(out);
+        }
+        "br" => {
+            trim_trailing_spaces(out);
+            out.push('\n');
+        }
+        "hr" => {
+            block_start(out);
+            out.push_str("---");
+            block_end(out);
+        }
+        "ul" | "ol" => {
+            block_start(out);
+            render_list(el, cx, out, name == "ol");
+            block_end(out);
+        }
+        "li" => {
+            // Liste dışında kalmış li
+            let body = element_to_markdown(el, cx);
+            if body.is_empty() { return; }
+            block_start(out);
+            push_list_item(out, "- ", &body);
+            block_end(out);
+        }
+        "pre" => render_code_block(el, out),
+        "code" | "kbd" | "samp" | "tt" => {
+            let code = readability::collapse_ws(&el.text().collect::<String>());
+            if code.is_empty() { return; }
+            let fence = if code.contains('`') { "``" } else { "`" };
+            out.push_str(&format!("{}{}{}", fence, code, fence));
+        }
+        "strong" | "b" => wrap_inline(el, cx, out, "**"),
+        "em" | "i" | "cite" => wrap_inline(el, cx, out, "*"),
+        "del" | "s" | "strike" => wrap_inline(el, cx, out, "~~"),
+        "a" => render_link(el, cx, out),
+        "img" => render_image(el, cx, out),
+        "blockquote" => {
+            let inner = element_to_markdown(el, cx);
+            if inner.is_empty() { return; }
+            block_start(out);
+            for line in inner.lines() {
+                if line.is_empty() { out.push_str(">\n"); } else { out.push_str("> "); out.push_str(line); out.push('\n'); }
+            }
+            block_end(out);
+        }
+        "table" => render_table(el, cx, out),
+        "dt" => {
+            let text = inline(el, cx);
+            if text.is_empty() { return; }
+            block_start(out);
+            out.push_str(&format!("**{}**", text));
+            out.push('\n');
+        }
+        "dd" => {
+            let text = inline(el, cx);
+            if text.is_empty() { return; }
+            trim_trailing_spaces(out);
+            if !out.is_empty() && !out.ends_with('\n') { out.push('\n'); }
+            out.push_str(": ");
+            out.push_str(&text);
+            block_end(out);
+        }
+        _ if BLOCK_TAGS.contains(&name) => {
+            block_start(out);
+            render_children(el, cx, out);
+            block_end(out);
+        }
+        _ => render_children(el, cx, out),
+    }
+}
+
+fn render_list(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String, ordered: bool) {
+    let mut idx: usize = el.value().attr("start").and_then(|s| s.trim().parse().ok()).unwrap_or(1);
+    for child in el.children().filter_map(ElementRef::wrap) {
+        if child.value().name() != "li" {
+            // ul içine doğrudan konmuş ul/ol gibi hatalı yapılar
+            render_element(child, cx, out);
+            continue;
+        }
+        let body = element_to_markdown(child, cx);
+        if body.is_empty() { continue; }
+        let marker = if ordered { format!("{}. ", idx) } else { "- ".to_string() };
+        push_list_item(out, &marker, &body);
+        idx += 1;
+    }
+}
+
+// Çok satırlı liste öğesini işaretçi genişliğinde girintiler (iç içe listeler korunur).
+fn push_list_item(out: &mut String, marker: &str, body: &str) {
+    let indent = " ".repeat(marker.len());
+    let mut first = true;
+    for line in body.lines() {
+        if line.trim().is_empty() { continue; }
+        if first {
+            out.push_str(marker);
+            first = false;
+        } else {
+            out.push_str(&indent);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+}
+
+fn render_code_block(el: ElementRef<'_>, out: &mut String) {
+    let code_sel = Selector::parse("code").ok();
+    let code_el = code_sel.as_ref().and_then(|s| el.select(s).next());
+    let lang = code_language(el).or_else(|| code_el.and_then(code_language)).unwrap_or_default();
+    let text: String = el.text().collect();
+    let text = text.trim_matches('\n').trim_end();
+    if text.trim().is_empty() { return; }
+    let fence = if text.contains("```") { "~~~~" } else { "```" };
+    block_start(out);
+    out.push_str(fence);
+    out.push_str(&lang);
+    out.push('\n');
+    out.push_str(text);
+    out.push('\n');
+    out.push_str(fence);
+    block_end(out);
+}
+
+// class="language-rust" / "lang-js" / "highlight-source-python" gibi ipuçlarından dil adı
+fn code_language(el: ElementRef<'_>) -> Option<String> {
+    if let Some(lang) = el.value().attr("data-lang").or_else(|| el.value().attr("data-language")) {
+        let l = lang.trim();
+        if !l.is_empty() { return Some(l.to_lowercase()); }
+    }
+    for class in el.value().classes() {
+        for prefix in ["language-", "lang-", "highlight-source-", "brush:"] {
+            if let Some(rest) = class.strip_prefix(prefix) {
+                if !rest.is_empty() { return Some(rest.to_lowercase()); }
+            }
+        }
+    }
+    None
+}
+
+fn render_link(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String) {
+    let text = inline(el, cx);
+    let href = el.value().attr("href").map(str::trim).unwrap_or("");
+    if text.is_empty() { return; }
+    let lower = href.to_lowercase();
+    if href.is_empty() || href.starts_with('#') || lower.starts_with("javascript:") {
+        out.push_str(&text);
+        return;
+    }
+    let resolved = resolve_url(cx.base, href);
+    out.push_str(&format!("[{}]({})", text.replace(']', "\\]"), resolved));
+}
+
+fn render_image(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String) {
+    let v = el.value();
+    let src = v.attr("src").or_else(|| v.attr("data-src")).map(str::trim).unwrap_or("");
+    if src.is_empty() || src.starts_with("data:") { return; }
+    let alt = readability::collapse_ws(v.attr("alt").unwrap_or(""));
+    out.push_str(&format!("![{}]({})", alt, resolve_url(cx.base, src)));
+}
+
+// Veri tabloları GFM tablosu olarak yazılır (thead, colspan/rowspan tables.rs'te);
+// tek sütunlu yerleşim tablolarının yalnızca içeriği alınır.
+fn render_table(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String) {
+    match tables::parse_table(el, Some(cx)) {
+        Some(table) => {
+            block_start(out);
+            out.push_str(&table.to_markdown());
+            block_end(out);
+        }
+        None => render_children(el, cx, out),
+    }
+}
+
+fn wrap_inline(el: ElementRef<'_>, cx: &Ctx<'_>, out: &mut String, marker: &str) {
+    let text = inline(el, cx);
+    if text.is_empty() { return; }
+    out.push_str(&format!("{}{}{}", marker, text, marker));
+}
+
+// Bir öğenin içeriğini tek satırlık satır içi Markdown olarak döndürür.
+pub(crate) fn inline(el: ElementRef<'_>, cx: &Ctx<'_>) -> String {
+    let mut buf = String::new();
+    render_children(el, cx, &mut buf);
+    readability::collapse_ws(&buf)
+}
+
+pub(crate) fn resolve_url(base: Option<&Url>, href: &str) -> String {
+    match base {
+        Some(b) => b.join(href).map(|u| u.to_string()).unwrap_or_else(|_| href.to_string()),
+        None => href.to_string(),
+    }
+}
+
+fn push_text(out: &mut String, raw: &str) {
+    let collapsed = readability::collapse_ws(raw);
+    let lead = raw.starts_with(char::is_whitespace);
+    let trail = raw.ends_with(char::is_whitespace);
+    if collapsed.is_empty() {
+        if lead && !at_line_start(out) && !out.ends_with(' ') { out.push(' '); }
+        return;
+    }
+    if lead && !at_line_start(out) && !out.ends_with(' ') { out.push(' '); }
+    out.push_str(&collapsed);
+    if trail { out.push(' '); }
+}
+
+fn at_line_start(out: &str) -> bool {
+    out.is_empty() || out.ends_with('\n')
+}
+
+fn trim_trailing_spaces(out: &mut String) {
+    while out.ends_with(' ') { out.pop(); }
+}
+
+fn block_start(out: &mut String) {
+    trim_trailing_spaces(out);
+    if out.is_empty() || out.ends_with("\n\n") { return; }
+    if out.ends_with('\n') { out.push('\n'); } else { out.push_str("\n\n"); }
+}
+
+fn block_end(out: &mut String) {
+    trim_trailing_spaces(out);
+    if !out.ends_with('\n') { out.push('\n'); }
+    out.push('\n');
+}
+
+// Satır sonu boşluklarını siler, kod blokları dışında ardışık boş satırları teke indirir.
+fn normalize(s: &str) -> String {
+    let mut out = String::new();
+    let mut blank = true;
+    let mut in_fence = false;
+    for line in s.lines() {
+        let trimmed = line.trim_end();
+        let t = trimmed.trim_start();
+        if t.starts_with("```") || t.starts_with("~~~~") { in_fence = !in_fence; }
+        if in_fence || t.starts_with("```") || t.starts_with("~~~~") {
+            out.push_str(trimmed);
+            out.push('\n');
+            blank = false;
+            continue;
+        }
+        if t.is_empty() {
+            if !blank { out.push('\n'); blank = true; }
+            continue;
+        }
+        out.push_str(trimmed);
+        out.push('\n');
+        blank = false;
+    }
+    out.trim().to_string()
+}
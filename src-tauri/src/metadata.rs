@@ -0,0 +1,197 @@
+// Makale meta verisi: başlık, yazarlar, yayın/güncelleme tarihi, yayıncı, canonical adres,
+// kelime sayısı ve okuma süresi. Kaynaklar öncelik sırasıyla Open Graph / article:* meta etiketleri,
+// schema.org makale nesnesi (JSON-LD, microdata, RDFa), <meta name="author|date"> ve <time> öğeleridir.
+// Tarihler chrono ile ayrıştırılıp RFC 3339'a (yalnızca gün biliniyorsa YYYY-AA-GG) çevrilir.
+// Sonuç model bağlamının başına eklenir ve content-source olayıyla frontend'e gider.
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::{readability, structured};
+
+// Ortalama okuma hızı (kelime/dk)
+const WORDS_PER_MINUTE: usize = 230;
+const MAX_AUTHORS: usize = 5;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageMetadata {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub published: Option<String>, // RFC 3339 ya da YYYY-AA-GG
+    pub modified: Option<String>,
+    pub publisher: Option<String>,
+    pub canonical: Option<String>,
+    pub word_count: usize,
+    pub reading_minutes: usize,
+}
+
+impl PageMetadata {
+    pub fn from_html(html: &str, page_url: &str) -> Self {
+        let doc = Html::parse_document(html);
+        let base = url::Url::parse(page_url).ok();
+        let article = structured::article_item(&doc);
+        let ld = |key: &str| article.as_ref().and_then(|a| structured::text(a.get(key)));
+
+        let title = meta(&doc, "property", "og:title")
+            .or_else(|| ld("headline"))
+            .or_else(|| readability::document_title(&doc))
+            .or_else(|| readability::tag_texts(&doc, "h1").into_iter().next());
+
+        let mut authors: Vec<String> = article.as_ref().map(|a| structured::texts(a.get("author"))).unwrap_or_default();
+        authors.extend(metas(&doc, "name", "author"));
+        // article:author çoğu zaman profil adresidir; yalnızca ad ise alınır
+        authors.extend(metas(&doc, "property", "article:author").into_iter().filter(|a| !a.starts_with("http")));
+        authors.extend(texts(&doc, "[rel~='author'], [itemprop='author'] [itemprop='name'], .byline .author, .author-name"));
+        let authors = dedupe(authors);
+
+        let published = [
+            meta(&doc, "property", "article:published_time"),
+            ld("datePublished"),
+            meta(&doc, "itemprop", "datePublished"),
+            meta(&doc, "name", "date"),
+            meta(&doc, "name", "pubdate"),
+            meta(&doc, "name", "publish-date"),
+            meta(&doc, "name", "dc.date.issued"),
+            first_time(&doc),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|d| normalize_date(&d));
+
+        let modified = [
+            meta(&doc, "property", "article:modified_time"),
+            meta(&doc, "property", "og:updated_time"),
+            ld("dateModified"),
+            meta(&doc, "itemprop", "dateModified"),
+            meta(&doc, "name", "last-modified"),
+        ]
+        .into_iter()
+        .flatten()
+        .find_map(|d| normalize_date(&d));
+
+        let publisher = ld("publisher").or_else(|| meta(&doc, "property", "og:site_name"));
+
+        let canonical = attr(&doc, "link[rel='canonical'][href]", "href")
+            .or_else(|| meta(&doc, "property", "og:url"))
+            .and_then(|h| match &base {
+                Some(b) => b.join(&h).ok().map(|u| u.to_string()),
+                None => Some(h),
+            });
+
+        // Kelime sayısı ana içerik düğümünden; bulunamazsa gövdenin tamamından
+        let body_text = readability::find_main_content(&doc)
+            .or_else(|| Selector::parse("body").ok().and_then(|s| doc.select(&s).next()))
+            .map(|el| el.text().collect::<String>())
+            .unwrap_or_default();
+
+        let mut m = PageMetadata { title, authors, published, modified, publisher, canonical, ..Default::default() };
+        m.set_word_count(count_words(&body_text));
+        m
+    }
+
+    // HTML'i olmayan kaynaklar (Firecrawl, PDF, çıkarıcılar): ilk başlık ve metnin kelime sayısı
+    pub fn from_text(text: &str) -> Self {
+        let title = text
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("# "))
+            .map(readability::collapse_ws)
+            .filter(|t| !t.is_empty());
+        let mut m = PageMetadata { title, ..Default::default() };
+        m.set_word_count(count_words(text));
+        m
+    }
+
+    fn set_word_count(&mut self, words: usize) {
+        self.word_count = words;
+        self.reading_minutes = if words == 0 { 0 } else { words.div_ceil(WORDS_PER_MINUTE) };
+    }
+
+    // Model bağlamının başına eklenen kısa bölüm
+    pub fn render(&self) -> String {
+        let mut out = String::from("Sayfa bilgileri:\n");
+        let mut field = |label: &str, value: Option<&str>| {
+            if let Some(v) = value.filter(|v| !v.is_empty()) { out.push_str(&format!("- {}: {}\n", label, v)); }
+        };
+        field("Başlık", self.title.as_deref());
+        field("Yazar", (!self.authors.is_empty()).then(|| self.authors.join(", ")).as_deref());
+        field("Yayın tarihi", self.published.as_deref());
+        field("Güncelleme tarihi", self.modified.as_deref());
+        field("Yayıncı", self.publisher.as_deref());
+        field("Adres (canonical)", self.canonical.as_deref());
+        if self.word_count > 0 {
+            out.push_str(&format!("- Uzunluk: {} kelime (~{} dk okuma)\n", self.word_count, self.reading_minutes));
+        }
+        out
+    }
+}
+
+fn meta(doc: &Html, key: &str, name: &str) -> Option<String> {
+    metas(doc, key, name).into_iter().next()
+}
+
+// <meta property|name|itemprop="..." content="...">; ad büyük/küçük harf duyarsız
+fn metas(doc: &Html, key: &str, name: &str) -> Vec<String> {
+    let Ok(sel) = Selector::parse(&format!("meta[{}][content]", key)) else { return Vec::new() };
+    doc.select(&sel)
+        .filter(|m| m.value().attr(key).map(|v| v.trim().eq_ignore_ascii_case(name)).unwrap_or(false))
+        .filter_map(|m| m.value().attr("content"))
+        .map(readability::collapse_ws)
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+fn attr(doc: &Html, sel: &str, name: &str) -> Option<String> {
+    let sel = Selector::parse(sel).ok()?;
+    doc.select(&sel).next().and_then(|e| e.value().attr(name)).map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+fn texts(doc: &Html, sel: &str) -> Vec<String> {
+    let Ok(sel) = Selector::parse(sel) else { return Vec::new() };
+    doc.select(&sel).map(|e| readability::collapse_ws(&e.text().collect::<String>())).filter(|t| !t.is_empty()).collect()
+}
+
+// Makale içindeki ilk <time datetime>, yoksa belgedeki ilk
+fn first_time(doc: &Html) -> Option<String> {
+    attr(doc, "article time[datetime]", "datetime").or_else(|| attr(doc, "time[datetime]", "datetime"))
+}
+
+// "Yazar: Ada Lovelace", "By Ada Lovelace" gibi ön ekleri atar, tekrarları eler
+fn dedupe(authors: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for a in authors {
+        let lower = a.to_lowercase();
+        let name = ["by ", "von ", "yazar:", "yazan:", "author:"]
+            .iter()
+            .find_map(|p| lower.starts_with(p).then(|| a[p.len()..].trim().to_string()))
+            .unwrap_or(a);
+        if name.is_empty() || name.chars().count() > 80 { continue; }
+        if out.iter().any(|x| x.eq_ignore_ascii_case(&name)) { continue; }
+        out.push(name);
+        if out.len() >= MAX_AUTHORS { break; }
+    }
+    out
+}
+
+// Sitelerde görülen tarih biçimleri; saat dilimi verilmemişse UTC varsayılmaz, dilimsiz yazılır
+fn normalize_date(raw: &str) -> Option<String> {
+    let s = raw.trim();
+    if s.is_empty() { return None; }
+    if let Ok(d) = DateTime::parse_from_rfc3339(s) { return Some(d.to_rfc3339()); }
+    if let Ok(d) = DateTime::parse_from_rfc2822(s) { return Some(d.to_rfc3339()); }
+    for fmt in ["%Y-%m-%dT%H:%M:%S%z", "%Y-%m-%dT%H:%M:%S%.f%z", "%Y-%m-%d %H:%M:%S%z"] {
+        if let Ok(d) = DateTime::parse_from_str(s, fmt) { return Some(d.to_rfc3339()); }
+    }
+    for fmt in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M", "%Y-%m-%d %H:%M"] {
+        if let Ok(d) = NaiveDateTime::parse_from_str(s, fmt) { return Some(d.format("%Y-%m-%dT%H:%M:%S").to_string()); }
+    }
+    for fmt in ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y", "%d/%m/%Y", "%Y%m%d", "%B %d, %Y", "%b %d, %Y", "%d %B %Y"] {
+        if let Ok(d) = NaiveDate::parse_from_str(s, fmt) { return Some(d.format("%Y-%m-%d").to_string()); }
+    }
+    None
+}
+
+fn count_words(text: &str) -> usize {
+    text.split_whitespace().filter(|w| w.chars().any(|c| c.is_alphanumeric())).count()
+}
@@ -0,0 +1,481 @@
+// Sayfa önbelleği: bellekte LRU katmanı ve kalıcı tablo.
+// Bellek katmanı en son kullanılan sayfaları bir bayt bütçesi ve kayıt sınırı içinde tutar; sınır
+// aşılınca en uzun süredir kullanılmayan kayıt atılır. Aynı sınırlar kalıcı tabloya da uygulanır
+// (en eski çekilen kayıt önce silinir). İsabet, ıska ve atma sayaçları tutulur.
+// Çıkarılmış içerik, kaynak etiketi, içerik özeti (SHA-256), ETag, Last-Modified ve çekilme zamanı
+// sohbet veritabanındaki page_cache tablosunda tutulur; uygulama yeniden açıldığında da geçerlidir.
+// Süresi dolan kayıt, sayfa kendi HTTP adımlarımızla çekildiyse (doğrulayıcıları varsa) koşullu GET
+// ile yeniden doğrulanır: 304 gelirse yeniden çıkarım yapılmadan yalnızca süre tazelenir, 200 gelirse
+// içerik aynı yanıtın gövdesinden yeniden çıkarılır (ikinci istek yapılmaz).
+// Sekme çerezleriyle çekilen sayfalar diske yazılmaz, yalnızca bellekte tutulur; kalıcı kayıtlar hep
+// çerezsizdir ve çerezli isteklere verilmez, yeniden doğrulamada da çerez gönderilmez.
+
+use chrono::Utc;
+use log::info;
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::http::Profile;
+use crate::{images, links, metadata, polite, ChatStore, ScrapedPage};
+
+const KEY_CACHE_CONFIG: &str = "page_cache_limits";
+
+// Bu süre içindeki kayıt doğrudan kullanılır; sonrası yeniden doğrulama ister
+pub const TTL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CacheConfig {
+    pub max_entries: usize,
+    pub max_bytes: usize, // içerik + bağlantılar + görsel adresleri, bayt
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { max_entries: 64, max_bytes: 32 * 1024 * 1024 }
+    }
+}
+
+impl CacheConfig {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_CACHE_CONFIG)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        if !(1..=10_000).contains(&self.max_entries) {
+            return Err("Önbellek kayıt sınırı 1 ile 10000 arasında olmalı".to_string());
+        }
+        if !(1024 * 1024..=1024 * 1024 * 1024).contains(&self.max_bytes) {
+            return Err("Önbellek bayt bütçesi 1 MB ile 1 GB arasında olmalı".to_string());
+        }
+        let raw = serde_json::to_string(self).map_err(|e| format!("Önbellek ayarı serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_CACHE_CONFIG, &raw)
+    }
+}
+
+struct Entry {
+    page: ScrapedPage,
+    fetched_at: Instant,
+    bytes: usize,
+    last_used: u64, // kullanım sırası; küçük olan daha eski
+}
+
+// Bellekteki LRU katmanı. Kayıt sayısı küçük tutulduğundan en eski kayıt doğrusal aramayla bulunur.
+#[derive(Default)]
+pub struct PageCache {
+    entries: HashMap<String, Entry>,
+    config: CacheConfig,
+    bytes: usize,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+    stored_hits: u64,
+    evictions: u64,
+}
+
+impl std::fmt::Debug for PageCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PageCache").field("entries", &self.entries.len()).field("bytes", &self.bytes).finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub hits: u64,        // bellekten karşılanan
+    pub misses: u64,      // bellekte yok ya da süresi dolmuş
+    pub stored_hits: u64, // bellekte ıska, kalıcı tablodan karşılanan (taze ya da 304)
+    pub evictions: u64,   // bellek ve kalıcı tablodan sınır nedeniyle atılan
+    pub stored_entries: usize, // kalıcı tablodaki kayıtlar
+    pub stored_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryInfo {
+    pub url: String,
+    pub source: String,
+    pub bytes: usize,
+    pub fetched_at: i64, // unix sn
+    pub fresh: bool,
+    pub revalidatable: bool, // ETag / Last-Modified var
+    pub in_memory: bool,
+}
+
+impl PageCache {
+    pub fn new(config: CacheConfig) -> Self {
+        Self { config, ..Default::default() }
+    }
+
+    pub fn configure(&mut self, config: CacheConfig) {
+        self.config = config;
+        self.evict(0);
+    }
+
+    pub fn config(&self) -> CacheConfig {
+        self.config.clone()
+    }
+
+    // TTL içindeki kayıt isabettir ve kullanım sırası güncellenir; süresi dolmuş kayıt atılır, ıska sayılır.
+    // `cookies`: istek sekme çerezleriyle mi yapılacak; çerezsiz kopya oturumlu isteğe (ya da tersi) verilmez.
+    pub fn get(&mut self, url: &str, cookies: bool) -> Option<ScrapedPage> {
+        self.clock += 1;
+        if self.entries.get(url).is_some_and(|e| e.fetched_at.elapsed() >= TTL) {
+            self.remove(url);
+        }
+        let Some(entry) = self.entries.get_mut(url).filter(|e| e.page.cookies == cookies) else {
+            self.misses += 1;
+            return None;
+        };
+        entry.last_used = self.clock;
+        self.hits += 1;
+        Some(entry.page.clone())
+    }
+
+    pub fn record_stored_hit(&mut self) {
+        self.stored_hits += 1;
+    }
+
+    pub fn record_evictions(&mut self, n: usize) {
+        self.evictions += n as u64;
+    }
+
+    pub fn insert(&mut self, url: &str, page: ScrapedPage) {
+        self.remove(url);
+        let bytes = page_size(&page);
+        // Tek başına bütçeyi aşan sayfa önbelleğe alınmaz
+        if bytes > self.config.max_bytes { return; }
+        self.evict(bytes);
+        self.clock += 1;
+        self.bytes += bytes;
+        self.entries.insert(url.to_string(), Entry { page, fetched_at: Instant::now(), bytes, last_used: self.clock });
+    }
+
+    pub fn remove(&mut self, url: &str) -> bool {
+        match self.entries.remove(url) {
+            Some(e) => {
+                self.bytes -= e.bytes;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn purge_domain(&mut self, domain: &str) -> usize {
+        let urls: Vec<String> = self.entries.keys().filter(|u| host_matches(u, domain)).cloned().collect();
+        urls.iter().filter(|u| self.remove(u)).count()
+    }
+
+    // Çerez paylaşımı kaldırılan sitelerin oturumlu (sekme çerezleriyle çekilmiş) sayfaları atılır
+    pub fn purge_cookie_pages(&mut self, allowed: impl Fn(&str) -> bool) -> usize {
+        let urls: Vec<String> = self.entries.iter().filter(|(u, e)| e.page.cookies && !allowed(u)).map(|(u, _)| u.clone()).collect();
+        urls.iter().filter(|u| self.remove(u)).count()
+    }
+
+    pub fn clear(&mut self) -> usize {
+        let n = self.entries.len();
+        self.entries.clear();
+        self.bytes = 0;
+        n
+    }
+
+    pub fn contains(&self, url: &str) -> bool {
+        self.entries.contains_key(url)
+    }
+
+    // Bellekteki kayıtların özetleri (kalıcı tabloda olmayan oturumlu sayfalar dahil)
+    pub fn list(&self) -> Vec<EntryInfo> {
+        let now = Utc::now().timestamp();
+        self.entries
+            .iter()
+            .map(|(url, e)| EntryInfo {
+                url: url.clone(),
+                source: e.page.source.clone(),
+                bytes: e.page.content.len(),
+                fetched_at: now - e.fetched_at.elapsed().as_secs() as i64,
+                fresh: e.fetched_at.elapsed() < TTL,
+                revalidatable: !e.page.validators.is_empty(),
+                in_memory: true,
+            })
+            .collect()
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len(),
+            bytes: self.bytes,
+            max_entries: self.config.max_entries,
+            max_bytes: self.config.max_bytes,
+            hits: self.hits,
+            misses: self.misses,
+            stored_hits: self.stored_hits,
+            evictions: self.evictions,
+            stored_entries: 0,
+            stored_bytes: 0,
+        }
+    }
+
+    // Yeni kayda yer açmak için en uzun süredir kullanılmayan kayıtlar atılır
+    fn evict(&mut self, incoming: usize) {
+        let reserve = usize::from(incoming > 0);
+        while !self.entries.is_empty()
+            && (self.entries.len() + reserve > self.config.max_entries || self.bytes + incoming > self.config.max_bytes)
+        {
+            let Some(oldest) = self.entries.iter().min_by_key(|(_, e)| e.last_used).map(|(u, _)| u.clone()) else { break };
+            self.remove(&oldest);
+            self.evictions += 1;
+        }
+    }
+}
+
+fn page_size(page: &ScrapedPage) -> usize {
+    page.content.len()
+        + page.links.iter().map(|l| l.url.len() + l.text.len()).sum::<usize>()
+        + page.images.iter().map(|i| i.url.len() + i.alt.as_ref().map_or(0, String::len)).sum::<usize>()
+}
+
+// "example.com" hem example.com hem alt alan adlarıyla eşleşir
+pub fn host_matches(url: &str, domain: &str) -> bool {
+    let domain = domain.trim().trim_start_matches("*.").trim_start_matches('.').to_lowercase();
+    let Some(host) = url::Url::parse(url).ok().and_then(|u| u.host_str().map(str::to_lowercase)) else { return false };
+    !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)))
+}
+
+pub const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS page_cache (
+    url TEXT PRIMARY KEY,
+    content TEXT NOT NULL,
+    source TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    etag TEXT,
+    last_modified TEXT,
+    fetched_at INTEGER NOT NULL,
+    links TEXT,    -- JSON: bağlam ekindeki bağlantılar
+    metadata TEXT, -- JSON: PageMetadata
+    cookies INTEGER NOT NULL DEFAULT 0, -- sekme çerezleriyle çekildi
+    images TEXT    -- JSON: aday görseller
+);";
+
+// Tablo oluşturulduktan sonra eklenen sütunlar; eski veritabanlarında sütun zaten varsa hata yok sayılır
+pub const MIGRATIONS: &[&str] = &[
+    "ALTER TABLE page_cache ADD COLUMN cookies INTEGER NOT NULL DEFAULT 0",
+    "ALTER TABLE page_cache ADD COLUMN images TEXT",
+];
+
+// Koşullu GET için yanıt başlıklarından alınan doğrulayıcılar
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |name: reqwest::header::HeaderName| {
+            headers.get(name).and_then(|v| v.to_str().ok()).map(|s| s.trim().to_string()).filter(|s| !s.is_empty())
+        };
+        Self { etag: get(ETAG), last_modified: get(LAST_MODIFIED) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub url: String,
+    pub content: String,
+    pub source: String,
+    pub content_hash: String,
+    pub validators: Validators,
+    pub fetched_at: i64, // unix sn
+    pub links: Vec<links::Link>,
+    pub images: Vec<images::Candidate>,
+    pub metadata: Option<metadata::PageMetadata>,
+    pub cookies: bool,
+}
+
+impl Record {
+    pub fn is_fresh(&self) -> bool {
+        Utc::now().timestamp() - self.fetched_at < TTL.as_secs() as i64
+    }
+}
+
+pub fn content_hash(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+impl ChatStore {
+    pub fn cache_get(&self, url: &str) -> Result<Option<Record>, String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT url, content, source, content_hash, etag, last_modified, fetched_at, links, metadata, cookies, images FROM page_cache WHERE url = ?1")
+            .map_err(|e| format!("page_cache select prepare: {}", e))?;
+        let mut rows = stmt.query(params![url]).map_err(|e| format!("page_cache select: {}", e))?;
+        let Some(row) = rows.next().map_err(|e| e.to_string())? else { return Ok(None) };
+        let links: Option<String> = row.get(7).unwrap_or(None);
+        let meta: Option<String> = row.get(8).unwrap_or(None);
+        let images: Option<String> = row.get(10).unwrap_or(None);
+        Ok(Some(Record {
+            url: row.get(0).map_err(|e| e.to_string())?,
+            content: row.get(1).map_err(|e| e.to_string())?,
+            source: row.get(2).map_err(|e| e.to_string())?,
+            content_hash: row.get(3).map_err(|e| e.to_string())?,
+            validators: Validators { etag: row.get(4).unwrap_or(None), last_modified: row.get(5).unwrap_or(None) },
+            fetched_at: row.get(6).unwrap_or(0),
+            links: links.and_then(|raw| serde_json::from_str(&raw).ok()).unwrap_or_default(),
+            images: images.and_then(|raw| serde_json::from_str(&raw).ok()).unwrap_or_default(),
+            metadata: meta.and_then(|raw| serde_json::from_str(&raw).ok()),
+            cookies: row.get(9).unwrap_or(false),
+        }))
+    }
+
+    pub fn cache_put(&self, record: &Record) -> Result<(), String> {
+        let links = serde_json::to_string(&record.links).map_err(|e| e.to_string())?;
+        let images = serde_json::to_string(&record.images).map_err(|e| e.to_string())?;
+        let meta = record.metadata.as_ref().and_then(|m| serde_json::to_string(m).ok());
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.execute(
+            "INSERT OR REPLACE INTO page_cache(url, content, source, content_hash, etag, last_modified, fetched_at, links, metadata, cookies, images)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                record.url, record.content, record.source, record.content_hash,
+                record.validators.etag, record.validators.last_modified, record.fetched_at, links, meta, record.cookies, images
+            ],
+        ).map_err(|e| format!("page_cache yazılamadı: {}", e))?;
+        Ok(())
+    }
+
+    // 304 sonrası: süre tazelenir, sunucu yeni doğrulayıcı gönderdiyse onlar saklanır
+    pub fn cache_touch(&self, url: &str, validators: &Validators) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.execute(
+            "UPDATE page_cache SET fetched_at = ?1, etag = COALESCE(?2, etag), last_modified = COALESCE(?3, last_modified) WHERE url = ?4",
+            params![Utc::now().timestamp(), validators.etag, validators.last_modified, url],
+        ).map_err(|e| format!("page_cache güncellenemedi: {}", e))?;
+        Ok(())
+    }
+
+    pub fn cache_delete(&self, url: &str) -> Result<(), String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.execute("DELETE FROM page_cache WHERE url = ?1", params![url])
+            .map_err(|e| format!("page_cache silinemedi: {}", e))?;
+        Ok(())
+    }
+
+    // İçerik olmadan kayıt özetleri, en yeni önce
+    pub fn cache_list(&self) -> Result<Vec<EntryInfo>, String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT url, source, LENGTH(CAST(content AS BLOB)), fetched_at, etag IS NOT NULL OR last_modified IS NOT NULL FROM page_cache ORDER BY fetched_at DESC")
+            .map_err(|e| format!("page_cache list prepare: {}", e))?;
+        let now = Utc::now().timestamp();
+        let rows = stmt
+            .query_map([], |row| {
+                let fetched_at: i64 = row.get(3)?;
+                Ok(EntryInfo {
+                    url: row.get(0)?,
+                    source: row.get(1)?,
+                    bytes: row.get::<_, i64>(2)? as usize,
+                    fetched_at,
+                    fresh: now - fetched_at < TTL.as_secs() as i64,
+                    revalidatable: row.get(4)?,
+                    in_memory: false,
+                })
+            })
+            .map_err(|e| format!("page_cache list: {}", e))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(|e| e.to_string())
+    }
+
+    // (kayıt sayısı, içerik baytı)
+    pub fn cache_totals(&self) -> Result<(usize, usize), String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.query_row("SELECT COUNT(*), COALESCE(SUM(LENGTH(CAST(content AS BLOB))), 0) FROM page_cache", [], |row| {
+            Ok((row.get::<_, i64>(0)? as usize, row.get::<_, i64>(1)? as usize))
+        })
+        .map_err(|e| format!("page_cache özet: {}", e))
+    }
+
+    pub fn cache_purge_domain(&self, domain: &str) -> Result<usize, String> {
+        let urls: Vec<String> = self.cache_list()?.into_iter().map(|e| e.url).filter(|u| host_matches(u, domain)).collect();
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        for url in &urls {
+            conn.execute("DELETE FROM page_cache WHERE url = ?1", params![url])
+                .map_err(|e| format!("page_cache silinemedi: {}", e))?;
+        }
+        Ok(urls.len())
+    }
+
+    // Kayıt sınırı ve bayt bütçesi kalıcı tabloya da uygulanır: en yeni kayıtlar sığdığı kadar kalır.
+    // Silinen kayıt sayısı döner.
+    pub fn cache_prune(&self, config: &CacheConfig) -> Result<usize, String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        let mut stmt = conn
+            .prepare("SELECT url, LENGTH(CAST(content AS BLOB)) FROM page_cache ORDER BY fetched_at DESC")
+            .map_err(|e| format!("page_cache prune prepare: {}", e))?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize)))
+            .map_err(|e| format!("page_cache prune: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        let (mut kept, mut bytes) = (0, 0);
+        let mut doomed = Vec::new();
+        for (url, size) in rows {
+            if kept < config.max_entries && bytes + size <= config.max_bytes {
+                kept += 1;
+                bytes += size;
+            } else {
+                doomed.push(url);
+            }
+        }
+        for url in &doomed {
+            conn.execute("DELETE FROM page_cache WHERE url = ?1", params![url])
+                .map_err(|e| format!("page_cache silinemedi: {}", e))?;
+        }
+        Ok(doomed.len())
+    }
+
+    pub fn cache_clear(&self) -> Result<usize, String> {
+        let conn = self.conn.lock().map_err(|_| "DB kilidi".to_string())?;
+        conn.execute("DELETE FROM page_cache", []).map_err(|e| format!("page_cache temizlenemedi: {}", e))
+    }
+}
+
+// Koşullu GET sonucu
+pub enum Revalidation {
+    NotModified(Validators),     // 304: sunucunun döndürdüğü (varsa yeni) doğrulayıcılar
+    Modified(reqwest::Response), // 200: sayfa değişmiş, gövde henüz okunmadı
+}
+
+// Koşullu GET. robots.txt ve hız sınırı normal istekler gibi uygulanır. Doğrulayıcılar çerezsiz
+// yanıttan geldiği için çerez gönderilmez.
+pub async fn revalidate(url: &str, validators: &Validators) -> Result<Revalidation, String> {
+    let p = polite::politeness();
+    p.admit(url).await?;
+    let mut req = p.request(url, Profile::Scraper);
+    if let Some(etag) = &validators.etag { req = req.header(IF_NONE_MATCH, etag); }
+    if let Some(lm) = &validators.last_modified { req = req.header(IF_MODIFIED_SINCE, lm); }
+    let resp = req.send().await.map_err(|e| format!("Yeniden doğrulama isteği başarısız: {}", e))?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        info!("Cache yeniden doğrulandı (304): {}", url);
+        return Ok(Revalidation::NotModified(Validators::from_headers(resp.headers())));
+    }
+    if !resp.status().is_success() {
+        return Err(format!("Yeniden doğrulama: HTTP {}", resp.status()));
+    }
+    info!("Cache yeniden doğrulama: sayfa değişmiş, yanıttan yeniden çıkarılacak: {}", url);
+    Ok(Revalidation::Modified(resp))
+}
@@ -0,0 +1,114 @@
+// PDF belgelerinden sayfa sayfa metin çıkarımı.
+// Belge bilgileri (başlık, yazar, sayfa sayısı) metnin önüne eklenir, her sayfa
+// "--- Sayfa N / M ---" işaretiyle başlar; böylece model cevapta sayfa numarası verebilir.
+
+use pdf_extract::{Document, Object};
+
+// Çok büyük belgelerde bağlamı şişirmemek için üst sınır (bayt)
+const MAX_OUTPUT_BYTES: usize = 60_000;
+
+// Content-Type application/pdf ise ya da gövde "%PDF-" ile başlıyorsa PDF kabul edilir.
+// Bazı sunucular PDF'i octet-stream olarak döndüğü için sihirli baytlara da bakılır; ancak imza
+// yalnızca gövdenin başında (BOM ve boşluktan sonra) aranır ve text/html yanıtlarda hiç aranmaz.
+// Böylece "%PDF-1.7" metnini anan bir HTML sayfası PDF ayrıştırıcısına gitmez.
+pub fn looks_like_pdf(content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let mime = content_type.and_then(|ct| ct.split(';').next()).map(|m| m.trim().to_ascii_lowercase());
+    match mime.as_deref() {
+        Some("application/pdf") => return true,
+        Some("text/html") | Some("application/xhtml+xml") => return false,
+        _ => {}
+    }
+    let body = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let start = body.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(body.len());
+    body[start..].starts_with(b"%PDF-")
+}
+
+pub fn extract_pdf(bytes: &[u8]) -> Result<String, String> {
+    let pages = pdf_extract::extract_text_from_mem_by_pages(bytes).map_err(|e| format!("PDF metni çıkarılamadı: {}", e))?;
+    let total = pages.len();
+
+    let mut out = String::new();
+    out.push_str("[PDF Belgesi]\n\n");
+    if let Ok(doc) = Document::load_mem(bytes) {
+        if let Some(title) = info_string(&doc, b"Title") { out.push_str(&format!("Başlık: {}\n", title)); }
+        if let Some(author) = info_string(&doc, b"Author") { out.push_str(&format!("Yazar: {}\n", author)); }
+        if let Some(subject) = info_string(&doc, b"Subject") { out.push_str(&format!("Konu: {}\n", subject)); }
+    }
+    out.push_str(&format!("Sayfa sayısı: {}\n\n", total));
+
+    let mut has_text = false;
+    for (i, page) in pages.iter().enumerate() {
+        let text = tidy_page(page);
+        out.push_str(&format!("--- Sayfa {} / {} ---\n", i + 1, total));
+        if text.is_empty() {
+            out.push_str("(metin yok - taranmış görüntü olabilir)\n\n");
+        } else {
+            has_text = true;
+            out.push_str(&text);
+            out.push_str("\n\n");
+        }
+        if out.len() > MAX_OUTPUT_BYTES {
+            out.push_str(&format!("[... {} sayfadan yalnızca ilk {} sayfa alındı]\n", total, i + 1));
+            break;
+        }
+    }
+
+    if !has_text {
+        return Err("PDF'te çıkarılabilir metin yok (taranmış belge olabilir)".to_string());
+    }
+    Ok(out)
+}
+
+// Belge bilgi sözlüğünden (trailer /Info) metin alanı okur
+fn info_string(doc: &Document, key: &[u8]) -> Option<String> {
+    let info = match doc.trailer.get(b"Info").ok()? {
+        Object::Reference(id) => doc.get_object(*id).ok()?,
+        other => other,
+    };
+    let value = match info.as_dict().ok()?.get(key).ok()? {
+        Object::Reference(id) => doc.get_object(*id).ok()?,
+        other => other,
+    };
+    match value {
+        Object::String(bytes, _) => {
+            let s = decode_pdf_string(bytes);
+            let s = s.trim();
+            if s.is_empty() { None } else { Some(s.to_string()) }
+        }
+        _ => None,
+    }
+}
+
+// PDF metin dizeleri: BOM'lu UTF-16BE ya da PDFDocEncoding (Latin-1'e yakın)
+fn decode_pdf_string(bytes: &[u8]) -> String {
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = bytes[2..].chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Ok(s) = std::str::from_utf8(bytes) { return s.to_string(); }
+    bytes.iter().map(|&b| b as char).collect()
+}
+
+// Satır sonu tirelerini birleştirir, art arda boş satırları teke indirir
+fn tidy_page(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank = 0;
+    for line in text.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            blank += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            let hyphenated = out.ends_with('-') && out.chars().rev().nth(1).map(char::is_alphabetic).unwrap_or(false);
+            if hyphenated && blank == 0 && line.trim_start().starts_with(char::is_lowercase) {
+                out.pop();
+            } else {
+                out.push_str(if blank > 0 { "\n\n" } else { "\n" });
+            }
+        }
+        blank = 0;
+        out.push_str(line.trim_start());
+    }
+    out
+}
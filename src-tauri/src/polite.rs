@@ -0,0 +1,347 @@
+// Nezaket katmanı: sitelere giden tüm arka plan istekleri (HTTP fallback, YouTube, site taraması)
+// buradan geçer. Sunucuya dürüst bir user-agent ile kendimizi tanıtır, robots.txt'yi host başına
+// önbelleğe alıp uygular, Crawl-delay'e uyar ve host başına token bucket ile istek hızını sınırlar.
+// Firecrawl / Ollama / OpenRouter gibi API çağrıları bu katmana girmez.
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::http::{self, Profile};
+use crate::ChatStore;
+
+const KEY_CONFIG: &str = "politeness";
+// robots.txt önbellek süresi; alınamadıysa (5xx / ağ hatası) bu kısa süre boyunca site tamamen
+// yasak sayılır (RFC 9309 §2.3.1.4) ve sonra yeniden denenir
+const ROBOTS_TTL: Duration = Duration::from_secs(60 * 60);
+const ROBOTS_RETRY_TTL: Duration = Duration::from_secs(10 * 60);
+// Çok büyük robots.txt dosyalarının yalnızca başı okunur
+const MAX_ROBOTS_BYTES: usize = 500 * 1024;
+// Aşırı Crawl-delay değerleri kullanıcıyı dakikalarca bekletmesin
+const MAX_CRAWL_DELAY: f64 = 10.0;
+
+pub fn default_user_agent() -> String {
+    format!("NexusBrowser/{} (AI okuma asistani; kullanici istegiyle sayfa okur)", env!("CARGO_PKG_VERSION"))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PoliteConfig {
+    pub user_agent: String,
+    pub respect_robots: bool,
+    // Host başına saniyede istek (token bucket dolum hızı) ve anlık patlama kapasitesi
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+impl Default for PoliteConfig {
+    fn default() -> Self {
+        Self { user_agent: default_user_agent(), respect_robots: true, requests_per_second: 1.0, burst: 3 }
+    }
+}
+
+impl PoliteConfig {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_CONFIG)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        if self.user_agent.trim().is_empty() {
+            return Err("User-agent boş olamaz".to_string());
+        }
+        if !(self.requests_per_second > 0.0 && self.requests_per_second <= 20.0) {
+            return Err("İstek hızı 0 ile 20 (istek/sn) arasında olmalı".to_string());
+        }
+        if self.burst == 0 || self.burst > 50 {
+            return Err("Patlama kapasitesi 1 ile 50 arasında olmalı".to_string());
+        }
+        let raw = serde_json::to_string(self).map_err(|e| format!("Nezaket ayarları serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_CONFIG, &raw)
+    }
+
+    // robots.txt grup eşlemesinde kullanılan ürün adı ("NexusBrowser/0.1 (...)" -> "nexusbrowser")
+    fn product_token(&self) -> String {
+        self.user_agent
+            .split(|c: char| c == '/' || c.is_whitespace())
+            .next()
+            .unwrap_or("")
+            .to_lowercase()
+    }
+}
+
+// ---- robots.txt ----
+
+#[derive(Debug, Clone, Default)]
+struct RobotsRules {
+    // (izin mi, desen); en uzun eşleşen desen kazanır, eşitlikte Allow
+    rules: Vec<(bool, String)>,
+    crawl_delay: Option<f64>,
+    unreachable: bool, // robots.txt alınamadığı için her şey yasak
+}
+
+impl RobotsRules {
+    fn unreachable() -> Self {
+        Self { rules: vec![(false, "/".to_string())], crawl_delay: None, unreachable: true }
+    }
+
+    fn allows(&self, path: &str) -> bool {
+        let mut best: Option<(usize, bool)> = None;
+        for (allow, pattern) in &self.rules {
+            if !pattern_matches(pattern, path) { continue; }
+            let len = pattern.len();
+            best = match best {
+                Some((l, a)) if l > len || (l == len && a) => Some((l, a)),
+                _ => Some((len, *allow)),
+            };
+        }
+        best.map(|(_, allow)| allow).unwrap_or(true)
+    }
+}
+
+// Google/RFC 9309 sözdizimi: '*' herhangi bir dizi, sondaki '$' yol sonu
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(p) => (p, true),
+        None => (pattern, false),
+    };
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut pos = 0usize;
+    for (i, part) in parts.iter().enumerate() {
+        if i == 0 {
+            if !path.starts_with(part) { return false; }
+            pos = part.len();
+        } else if i == parts.len() - 1 && anchored {
+            return path.len() >= pos + part.len() && path.ends_with(part);
+        } else {
+            match path[pos..].find(part) {
+                Some(found) => pos += found + part.len(),
+                None => return false,
+            }
+        }
+    }
+    !anchored || pos == path.len()
+}
+
+// Bizim ürün adımızı içeren en özel grubu, yoksa '*' grubunu seçer.
+// Art arda gelen User-agent satırları aynı grubu paylaşır.
+fn parse_robots(text: &str, product: &str) -> RobotsRules {
+    let mut groups: Vec<(Vec<String>, RobotsRules)> = Vec::new();
+    let mut in_agents = false;
+    for line in text.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        let Some((key, value)) = line.split_once(':') else { continue };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        match key.as_str() {
+            "user-agent" => {
+                if !in_agents || groups.is_empty() {
+                    groups.push((Vec::new(), RobotsRules::default()));
+                }
+                in_agents = true;
+                if let Some((agents, _)) = groups.last_mut() { agents.push(value.to_lowercase()); }
+            }
+            "allow" | "disallow" => {
+                in_agents = false;
+                let Some((_, rules)) = groups.last_mut() else { continue };
+                // Boş Disallow "her şey serbest" demektir
+                if value.is_empty() { continue; }
+                rules.rules.push((key == "allow", value.to_string()));
+            }
+            "crawl-delay" => {
+                in_agents = false;
+                let Some((_, rules)) = groups.last_mut() else { continue };
+                rules.crawl_delay = value.parse::<f64>().ok().filter(|d| d.is_finite() && *d >= 0.0);
+            }
+            _ => {}
+        }
+    }
+
+    let mut chosen: Option<(usize, RobotsRules)> = None;
+    for (agents, rules) in groups {
+        for agent in &agents {
+            let score = if agent == "*" {
+                1
+            } else if !product.is_empty() && product.contains(agent.as_str()) {
+                agent.len() + 1
+            } else {
+                continue;
+            };
+            if chosen.as_ref().map(|(s, _)| score > *s).unwrap_or(true) {
+                chosen = Some((score, rules.clone()));
+            }
+        }
+    }
+    chosen.map(|(_, rules)| rules).unwrap_or_default()
+}
+
+struct CachedRobots {
+    rules: RobotsRules,
+    expires: Instant,
+}
+
+// ---- token bucket ----
+
+struct Bucket {
+    tokens: f64,
+    last: Instant,
+}
+
+impl Bucket {
+    // Bir jeton ayırır ve beklenmesi gereken süreyi döner. Jeton sayısı eksiye düşebilir;
+    // böylece eşzamanlı istekler sıraya girer ve her biri kendi payını bekler.
+    fn reserve(&mut self, rate: f64, capacity: f64) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * rate).min(capacity);
+        self.last = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 { Duration::ZERO } else { Duration::from_secs_f64(-self.tokens / rate) }
+    }
+}
+
+// ---- paylaşılan durum ----
+
+pub struct Politeness {
+    config: RwLock<PoliteConfig>,
+    robots: Mutex<HashMap<String, CachedRobots>>, // köken -> robots.txt kuralları
+    buckets: Mutex<HashMap<String, Bucket>>,      // host -> token bucket
+}
+
+static POLITENESS: OnceLock<Politeness> = OnceLock::new();
+
+pub fn politeness() -> &'static Politeness {
+    POLITENESS.get_or_init(|| Politeness {
+        config: RwLock::new(PoliteConfig::default()),
+        robots: Mutex::new(HashMap::new()),
+        buckets: Mutex::new(HashMap::new()),
+    })
+}
+
+impl Politeness {
+    pub fn config(&self) -> PoliteConfig {
+        self.config.read().map(|c| c.clone()).unwrap_or_default()
+    }
+
+    // Ayar değişince robots önbelleği de boşaltılır (grup seçimi user-agent'a bağlı)
+    pub fn configure(&self, config: PoliteConfig) {
+        if let Ok(mut c) = self.config.write() { *c = config; }
+        if let Ok(mut r) = self.robots.lock() { r.clear(); }
+    }
+
+    // Paylaşılan istemci üzerinden, yapılandırılmış user-agent ile GET isteği
+    pub fn request(&self, url: &str, profile: Profile) -> reqwest::RequestBuilder {
+        http::shared().get(url, profile).header(reqwest::header::USER_AGENT, self.config().user_agent)
+    }
+
+    // İstekten önce çağrılır: robots.txt izin vermiyorsa hata döner, izin veriyorsa
+    // host'un hız sınırı (ve Crawl-delay) kadar bekler.
+    pub async fn admit(&self, url: &str) -> Result<(), String> {
+        let parsed = url::Url::parse(url).map_err(|e| format!("Geçersiz URL: {}", e))?;
+        let Some(host) = parsed.host_str().map(|h| h.to_lowercase()) else { return Ok(()) };
+        let config = self.config();
+
+        let mut crawl_delay = None;
+        if config.respect_robots {
+            let rules = self.robots_for(&parsed, &config).await;
+            let mut path = parsed.path().to_string();
+            if let Some(q) = parsed.query() {
+                path.push('?');
+                path.push_str(q);
+            }
+            if rules.unreachable {
+                return Err(format!("robots.txt alınamadı (sunucu hatası); site bir süre taranmayacak: {}", url));
+            }
+            if !rules.allows(&path) {
+                return Err(format!("robots.txt bu adrese erişime izin vermiyor: {}", url));
+            }
+            crawl_delay = rules.crawl_delay;
+        }
+
+        self.wait_turn(&host, &config, crawl_delay).await;
+        Ok(())
+    }
+
+    async fn wait_turn(&self, host: &str, config: &PoliteConfig, crawl_delay: Option<f64>) {
+        // Crawl-delay varsa hız ona göre düşürülür ve patlamaya izin verilmez
+        let (rate, capacity) = match crawl_delay {
+            Some(d) if d > 0.0 => {
+                let d = d.min(MAX_CRAWL_DELAY);
+                ((1.0 / d).min(config.requests_per_second), 1.0)
+            }
+            _ => (config.requests_per_second, config.burst as f64),
+        };
+        let wait = {
+            let Ok(mut buckets) = self.buckets.lock() else { return };
+            buckets
+                .entry(host.to_string())
+                .or_insert_with(|| Bucket { tokens: capacity, last: Instant::now() })
+                .reserve(rate, capacity)
+        };
+        if !wait.is_zero() {
+            info!("Hız sınırı: {} için {} ms bekleniyor", host, wait.as_millis());
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    async fn robots_for(&self, url: &url::Url, config: &PoliteConfig) -> RobotsRules {
+        let origin = url.origin().ascii_serialization();
+        if let Ok(cache) = self.robots.lock() {
+            if let Some(entry) = cache.get(&origin).filter(|e| e.expires > Instant::now()) {
+                return entry.rules.clone();
+            }
+        }
+
+        let (rules, ttl) = match fetch_robots(&origin, config).await {
+            Ok(Some(text)) => (parse_robots(&text, &config.product_token()), ROBOTS_TTL),
+            // 4xx: robots.txt yok, her şey serbest
+            Ok(None) => (RobotsRules::default(), ROBOTS_TTL),
+            // 5xx / ağ hatası: erişilemeyen robots.txt tam yasak demektir
+            Err(e) => {
+                warn!("robots.txt alınamadı: {} ({}) - {} sn boyunca erişim yok", origin, e, ROBOTS_RETRY_TTL.as_secs());
+                (RobotsRules::unreachable(), ROBOTS_RETRY_TTL)
+            }
+        };
+        if let Ok(mut cache) = self.robots.lock() {
+            cache.insert(origin, CachedRobots { rules: rules.clone(), expires: Instant::now() + ttl });
+        }
+        rules
+    }
+}
+
+async fn fetch_robots(origin: &str, config: &PoliteConfig) -> Result<Option<String>, String> {
+    let resp = http::shared()
+        .get(&format!("{}/robots.txt", origin), Profile::Metadata)
+        .header(reqwest::header::USER_AGENT, config.user_agent.clone())
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    let status = resp.status();
+    if status.is_client_error() { return Ok(None); }
+    if !status.is_success() { return Err(format!("HTTP {}", status)); }
+    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+    let bytes = &bytes[..bytes.len().min(MAX_ROBOTS_BYTES)];
+    Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+// Kısa yol: izin al, yapılandırılmış user-agent ile GET at
+pub async fn get(url: &str, profile: Profile) -> Result<reqwest::Response, String> {
+    get_with_cookies(url, profile, None).await
+}
+
+// Sekme çerezleriyle GET (yalnızca çerez paylaşımına izin verilmiş alan adları için).
+// reqwest, başka bir host'a yönlendirmede Cookie başlığını düşürür.
+pub async fn get_with_cookies(url: &str, profile: Profile, cookie: Option<&str>) -> Result<reqwest::Response, String> {
+    let p = politeness();
+    p.admit(url).await?;
+    let mut req = p.request(url, profile);
+    if let Some(cookie) = cookie {
+        req = req.header(reqwest::header::COOKIE, cookie);
+    }
+    req.send().await.map_err(|e| format!("HTTP isteği başarısız: {}", e))
+}
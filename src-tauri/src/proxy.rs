@@ -0,0 +1,189 @@
+// Kurumsal ağlar için HTTP/HTTPS/SOCKS5 vekil sunucu ayarları.
+// Ayar app_settings'te saklanır; paylaşılan HTTP istemcisi (http.rs) `client_builder` üzerinden, yeni sekme webview'leri `webview_proxy_url` üzerinden vekili kullanır.
+// no-proxy listesindeki adresler (varsayılan olarak yerel Ollama) vekile gitmez.
+
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::ChatStore;
+
+const KEY_PROXY: &str = "proxy";
+// Çıkış IP'sini de döndüren hafif bir uç nokta
+const TEST_URL: &str = "https://www.cloudflare.com/cdn-cgi/trace";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProxyConfig {
+    pub enabled: bool,
+    pub kind: String, // http | https | socks5
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    // Virgülle ayrılmış host / alan adı / CIDR listesi (NO_PROXY biçimi)
+    pub no_proxy: String,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: "http".to_string(),
+            host: String::new(),
+            port: 8080,
+            username: None,
+            password: None,
+            no_proxy: "localhost, 127.0.0.1, ::1".to_string(),
+        }
+    }
+}
+
+impl ProxyConfig {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_PROXY)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        self.validate()?;
+        let raw = serde_json::to_string(self).map_err(|e| format!("Vekil ayarları serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_PROXY, &raw)
+    }
+
+    // Webview'a giden kopya: parola yalnızca kayıtlı olduğunu gösteren maskeyle gider
+    pub fn masked(mut self) -> Self {
+        if self.password.as_deref().is_some_and(|p| !p.is_empty()) {
+            self.password = Some(crate::SECRET_MASK.to_string());
+        }
+        self
+    }
+
+    // Arayüzden maske geri geldiyse parola değişmemiştir; kayıtlı parola kullanılır
+    pub fn with_stored_password(mut self, stored: &ProxyConfig) -> Self {
+        if self.password.as_deref() == Some(crate::SECRET_MASK) {
+            self.password = stored.password.clone();
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !matches!(self.kind.as_str(), "http" | "https" | "socks5") {
+            return Err(format!("Desteklenmeyen vekil türü: {} (http, https ya da socks5)", self.kind));
+        }
+        if !self.enabled { return Ok(()); }
+        let host = self.host.trim();
+        if host.is_empty() || host.contains(['/', ' ', '@']) {
+            return Err("Vekil sunucu adresi geçersiz (yalnızca host adı ya da IP)".to_string());
+        }
+        if self.port == 0 { return Err("Vekil portu 1-65535 olmalı".to_string()); }
+        self.url().map(|_| ())
+    }
+
+    // Kimlik bilgileri URL'ye yüzde kodlamasıyla gömülür. socks5h: DNS çözümlemesi de vekilde yapılır
+    // (kurumsal ağlarda dış adlar çoğu zaman yerelde çözülemez).
+    pub fn url(&self) -> Result<url::Url, String> {
+        let scheme = if self.kind == "socks5" { "socks5h" } else { self.kind.as_str() };
+        let mut u = url::Url::parse(&format!("{}://{}:{}", scheme, self.host.trim(), self.port))
+            .map_err(|e| format!("Vekil adresi geçersiz: {}", e))?;
+        if let Some(user) = self.username.as_deref().filter(|u| !u.is_empty()) {
+            u.set_username(user).map_err(|_| "Vekil kullanıcı adı ayarlanamadı".to_string())?;
+            u.set_password(self.password.as_deref().filter(|p| !p.is_empty()))
+                .map_err(|_| "Vekil parolası ayarlanamadı".to_string())?;
+        }
+        Ok(u)
+    }
+
+    fn reqwest_proxy(&self) -> Result<Option<reqwest::Proxy>, String> {
+        if !self.enabled { return Ok(None); }
+        let proxy = reqwest::Proxy::all(self.url()?.as_str()).map_err(|e| format!("Vekil ayarlanamadı: {}", e))?;
+        Ok(Some(proxy.no_proxy(reqwest::NoProxy::from_string(&self.no_proxy))))
+    }
+
+    fn apply(&self, builder: reqwest::ClientBuilder) -> Result<reqwest::ClientBuilder, String> {
+        Ok(match self.reqwest_proxy()? {
+            Some(proxy) => builder.proxy(proxy),
+            None => builder,
+        })
+    }
+}
+
+// ---- paylaşılan durum ----
+
+static PROXY: OnceLock<RwLock<ProxyConfig>> = OnceLock::new();
+
+fn cell() -> &'static RwLock<ProxyConfig> {
+    PROXY.get_or_init(|| RwLock::new(ProxyConfig::default()))
+}
+
+pub fn current() -> ProxyConfig {
+    cell().read().map(|c| c.clone()).unwrap_or_default()
+}
+
+pub fn configure(config: ProxyConfig) {
+    if config.enabled {
+        info!("Vekil sunucu etkin: {}://{}:{}", config.kind, config.host, config.port);
+    }
+    if let Ok(mut c) = cell().write() { *c = config; }
+}
+
+// Geçerli vekil ayarı uygulanmış istemci oluşturucu; tüm dış istekler buradan geçer
+pub fn client_builder() -> Result<reqwest::ClientBuilder, String> {
+    current().apply(reqwest::Client::builder())
+}
+
+// Sekme webview'leri için. WebView kimlik doğrulamalı vekil desteklemediği için kullanıcı adı/parola eklenmez;
+// https vekiller webview'e http olarak verilir (CONNECT tüneli aynıdır).
+pub fn webview_proxy_url() -> Option<url::Url> {
+    let config = current();
+    if !config.enabled { return None; }
+    let scheme = if config.kind == "socks5" { "socks5" } else { "http" };
+    url::Url::parse(&format!("{}://{}:{}", scheme, config.host.trim(), config.port)).ok()
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyTestResult {
+    pub ok: bool,
+    pub status: Option<u16>,
+    pub latency_ms: u64,
+    pub exit_ip: Option<String>,
+    pub error: Option<String>,
+}
+
+// Verilen ayarla (kaydetmeden) test adresine istek atar; bağlantı hatası sonuç olarak döner
+pub async fn test(config: &ProxyConfig) -> Result<ProxyTestResult, String> {
+    config.validate()?;
+    let client = config
+        .apply(reqwest::Client::builder())?
+        .timeout(Duration::from_secs(10))
+        .build()
+        .map_err(|e| format!("HTTP client oluşturulamadı: {}", e))?;
+    let started = Instant::now();
+    let result = match client.get(TEST_URL).send().await {
+        Ok(resp) => {
+            let status = resp.status();
+            let body = resp.text().await.unwrap_or_default();
+            let exit_ip = body.lines().find_map(|l| l.strip_prefix("ip=")).map(|ip| ip.trim().to_string());
+            ProxyTestResult {
+                ok: status.is_success(),
+                status: Some(status.as_u16()),
+                latency_ms: started.elapsed().as_millis() as u64,
+                exit_ip,
+                error: (!status.is_success()).then(|| format!("HTTP {}", status)),
+            }
+        }
+        Err(e) => ProxyTestResult {
+            ok: false,
+            status: None,
+            latency_ms: started.elapsed().as_millis() as u64,
+            exit_ip: None,
+            error: Some(e.to_string()),
+        },
+    };
+    Ok(result)
+}
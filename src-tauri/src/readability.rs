@@ -0,0 +1,174 @@
+// DOM tabanlı okunabilirlik (readability) çıkarımı.
+// Sayfa gerçek bir HTML ayrıştırıcısı ile ağaca çevrilir, paragraflar üst
+// düğümlerine puan taşır ve en yüksek puanlı düğüm ana içerik kabul edilir.
+// Menü, footer, sidebar gibi bölümler hem etiket hem class/id ile elenir.
+
+use scraper::{ElementRef, Html, Selector};
+use std::collections::HashMap;
+
+// İçeriğe hiç katılmayacak etiketler
+const SKIP_TAGS: &[&str] = &[
+    "script", "style", "noscript", "template", "iframe", "svg", "canvas", "form", "button",
+    "input", "select", "textarea", "nav", "footer", "aside", "head",
+];
+
+const NEGATIVE_HINTS: &[&str] = &[
+    "nav", "menu", "footer", "sidebar", "side-bar", "widget", "comment", "share", "social",
+    "related", "recommend", "promo", "advert", "sponsor", "banner", "cookie", "consent",
+    "popup", "modal", "newsletter", "subscribe", "breadcrumb", "pagination", "masthead",
+    "toolbar", "skip",
+];
+
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "content", "main", "post", "entry", "story", "text", "body", "blog", "haber",
+    "icerik", "detail",
+];
+
+pub fn document_title(doc: &Html) -> Option<String> {
+    let sel = Selector::parse("title").ok()?;
+    let t = doc.select(&sel).next().map(|e| collapse_ws(&e.text().collect::<String>()))?;
+    if t.is_empty() { None } else { Some(t) }
+}
+
+// Belgedeki belirli bir etiketin (örn. h2) görünür metinlerini sırayla döndürür.
+pub fn tag_texts(doc: &Html, tag: &str) -> Vec<String> {
+    let sel = match Selector::parse(tag) { Ok(s) => s, Err(_) => return Vec::new() };
+    doc.select(&sel)
+        .filter(|e| !is_unlikely(*e))
+        .map(|e| collapse_ws(&e.text().collect::<String>()))
+        .filter(|t| !t.is_empty())
+        .collect()
+}
+
+// Readability benzeri puanlama ile ana içerik düğümünü bulur.
+pub fn find_main_content(doc: &Html) -> Option<ElementRef<'_>> {
+    let para_sel = Selector::parse("p, pre, td, blockquote, li").ok()?;
+    let mut scores: HashMap<ego_tree::NodeId, f64> = HashMap::new();
+    let mut candidates: Vec<ElementRef<'_>> = Vec::new();
+
+    for p in doc.select(&para_sel) {
+        if has_unlikely_ancestor(p) { continue; }
+        let text = collapse_ws(&p.text().collect::<String>());
+        let len = text.chars().count();
+        if len < 25 { continue; }
+
+        // Temel puan + virgül sayısı + uzunluk bonusu (en fazla 3)
+        let commas = text.matches(&[',', '،', '、'][..]).count() as f64;
+        let content_score = 1.0 + commas + ((len / 100) as f64).min(3.0);
+
+        let parent = p.parent().and_then(ElementRef::wrap);
+        let grand = parent.and_then(|e| e.parent()).and_then(ElementRef::wrap);
+        for (ancestor, divider) in [(parent, 1.0), (grand, 2.0)] {
+            if let Some(a) = ancestor {
+                let entry = scores.entry(a.id()).or_insert_with(|| {
+                    candidates.push(a);
+                    initial_score(a)
+                });
+                *entry += content_score / divider;
+            }
+        }
+    }
+
+    let mut best: Option<(ElementRef<'_>, f64)> = None;
+    for c in candidates {
+        let raw = scores.get(&c.id()).copied().unwrap_or(0.0);
+        let score = raw * (1.0 - link_density(c));
+        if best.map(|(_, s)| score > s).unwrap_or(true) {
+            best = Some((c, score));
+        }
+    }
+
+    if let Some((el, _)) = best {
+        return Some(el);
+    }
+
+    // Puanlanacak paragraf yoksa semantik etiketlere düş
+    for fallback in ["article", "main", "[role=main]", "body"] {
+        if let Ok(sel) = Selector::parse(fallback) {
+            if let Some(el) = doc.select(&sel).next() { return Some(el); }
+        }
+    }
+    None
+}
+
+fn initial_score(el: ElementRef<'_>) -> f64 {
+    let base = match el.value().name() {
+        "article" => 10.0,
+        "div" | "section" | "main" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "address" | "ol" | "ul" | "dl" | "dd" | "dt" | "li" | "form" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    base + class_weight(el)
+}
+
+fn class_weight(el: ElementRef<'_>) -> f64 {
+    let mut weight = 0.0;
+    for attr in ["class", "id"] {
+        if let Some(v) = el.value().attr(attr) {
+            let v = v.to_lowercase();
+            if NEGATIVE_HINTS.iter().any(|h| v.contains(h)) { weight -= 25.0; }
+            if POSITIVE_HINTS.iter().any(|h| v.contains(h)) { weight += 25.0; }
+        }
+    }
+    weight
+}
+
+// Bağlantı metninin toplam metne oranı (menü benzeri blokları cezalandırmak için)
+pub fn link_density(el: ElementRef<'_>) -> f64 {
+    let total = el.text().map(|t| t.chars().filter(|c| !c.is_whitespace()).count()).sum::<usize>();
+    if total == 0 { return 0.0; }
+    let a_sel = match Selector::parse("a") { Ok(s) => s, Err(_) => return 0.0 };
+    let linked = el
+        .select(&a_sel)
+        .map(|a| a.text().map(|t| t.chars().filter(|c| !c.is_whitespace()).count()).sum::<usize>())
+        .sum::<usize>();
+    (linked as f64 / total as f64).min(1.0)
+}
+
+// Etiket adı, role ya da class/id ipuçlarına göre içerik dışı sayılan öğe mi?
+pub fn is_unlikely(el: ElementRef<'_>) -> bool {
+    let v = el.value();
+    if SKIP_TAGS.contains(&v.name()) { return true; }
+    if v.attr("hidden").is_some() || v.attr("aria-hidden") == Some("true") { return true; }
+    if let Some(role) = v.attr("role") {
+        if matches!(role, "navigation" | "banner" | "contentinfo" | "complementary" | "dialog") { return true; }
+    }
+    if let Some(style) = v.attr("style") {
+        let s = style.replace(' ', "").to_lowercase();
+        if s.contains("display:none") || s.contains("visibility:hidden") { return true; }
+    }
+    // body/html/main/article gibi kapsayıcıları class adıyla eleme
+    if matches!(v.name(), "html" | "body" | "main" | "article") { return false; }
+    let hint = format!("{} {}", v.attr("class").unwrap_or(""), v.attr("id").unwrap_or("")).to_lowercase();
+    if hint.trim().is_empty() { return false; }
+    let negative = NEGATIVE_HINTS.iter().any(|h| hint.contains(h));
+    let positive = POSITIVE_HINTS.iter().any(|h| hint.contains(h));
+    negative && !positive
+}
+
+pub(crate) fn has_unlikely_ancestor(el: ElementRef<'_>) -> bool {
+    if is_unlikely(el) { return true; }
+    el.ancestors().filter_map(ElementRef::wrap).any(is_unlikely)
+}
+
+pub fn collapse_ws(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// UTF-8 karakter sınırına saygı göstererek en fazla `max_bytes` uzunluğunda önek döndürür.
+pub fn truncate_utf8(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes { return s; }
+    let mut end = max_bytes;
+    while end > 0 && !s.is_char_boundary(end) { end -= 1; }
+    &s[..end]
+}
+
+// En fazla `max` karakter; kesildiyse sona "…" eklenir (özet ve bağlantı metinleri için)
+pub fn truncate_chars(s: &str, max: usize) -> String {
+    match s.char_indices().nth(max) {
+        Some((idx, _)) => format!("{}…", s[..idx].trim_end()),
+        None => s.to_string(),
+    }
+}
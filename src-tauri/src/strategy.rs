@@ -0,0 +1,274 @@
+// Alan adına göre yapılandırılabilir içerik çıkarma zinciri.
+// Her kalıp (örn. "*.example.com", "docs.rs", "*") için adımların sırası, açık/kapalı durumu ve
+// adım başına zaman aşımı app_settings'te saklanır. Zincirdeki her adımın sonucu (süre, sonuç, hata)
+// bir deneme raporuna yazılır; rapor content-source olayıyla frontend'e gider.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::ChatStore;
+
+const KEY_CHAINS: &str = "strategy_chains";
+const KEY_RACE: &str = "strategy_race";
+// Eski genel içerik kaynağı ayarı (auto | live_dom); açılışta zincire taşınıp silinir
+const KEY_LEGACY_CONTENT_STRATEGY: &str = "content_strategy";
+
+// Bilinen adımlar ve varsayılan sıra
+pub const STEP_LIVE_DOM: &str = "live_dom"; // açık sekmenin render edilmiş DOM'u
+pub const STEP_EXTRACTORS: &str = "extractors"; // siteye özel çıkarıcılar (YouTube, PDF...)
+pub const STEP_FIRECRAWL_SCRAPE: &str = "firecrawl_scrape";
+pub const STEP_FIRECRAWL_CRAWL: &str = "firecrawl_crawl";
+pub const STEP_AGGRESSIVE_HTML: &str = "aggressive_html";
+pub const STEP_HTTP_FALLBACK: &str = "http_fallback";
+
+// (adım, zaman aşımı sn, varsayılan olarak açık mı)
+const DEFAULT_STEPS: &[(&str, u64, bool)] = &[
+    (STEP_LIVE_DOM, 5, false),
+    (STEP_EXTRACTORS, 30, true),
+    (STEP_FIRECRAWL_SCRAPE, 30, true),
+    (STEP_FIRECRAWL_CRAWL, 75, true),
+    (STEP_AGGRESSIVE_HTML, 20, true),
+    (STEP_HTTP_FALLBACK, 20, true),
+];
+
+const MAX_TIMEOUT_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepConfig {
+    pub name: String,
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+    pub timeout_secs: u64,
+}
+
+fn enabled_default() -> bool { true }
+
+impl StepConfig {
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DomainChain {
+    // "*" tüm siteler, "*.example.com" alt alan adları dahil, "example.com" yalnızca kendisi
+    pub pattern: String,
+    pub steps: Vec<StepConfig>,
+}
+
+pub fn default_steps() -> Vec<StepConfig> {
+    DEFAULT_STEPS
+        .iter()
+        .map(|(name, secs, enabled)| StepConfig { name: name.to_string(), enabled: *enabled, timeout_secs: *secs })
+        .collect()
+}
+
+pub fn load_chains(store: &ChatStore) -> Vec<DomainChain> {
+    store
+        .get_setting(KEY_CHAINS)
+        .unwrap_or(None)
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_chains(store: &ChatStore, chains: &[DomainChain]) -> Result<(), String> {
+    for chain in chains {
+        let pattern = chain.pattern.trim();
+        if pattern.is_empty() {
+            return Err("Alan adı kalıbı boş olamaz".to_string());
+        }
+        if pattern != "*" && pattern.trim_start_matches("*.").contains(['*', '/', ' ']) {
+            return Err(format!("Geçersiz alan adı kalıbı: {}", pattern));
+        }
+        for step in &chain.steps {
+            if !DEFAULT_STEPS.iter().any(|(name, ..)| *name == step.name) {
+                return Err(format!("Bilinmeyen adım: {} ({})", step.name, pattern));
+            }
+            if step.timeout_secs == 0 || step.timeout_secs > MAX_TIMEOUT_SECS {
+                return Err(format!("{} için zaman aşımı 1-{} sn olmalı ({})", step.name, MAX_TIMEOUT_SECS, pattern));
+            }
+        }
+    }
+    let raw = serde_json::to_string(chains).map_err(|e| format!("Strateji zinciri serileştirilemedi: {}", e))?;
+    store.set_setting(KEY_CHAINS, &raw)
+}
+
+// URL'nin host'una uyan en özel kalıbın adımları; hiçbiri uymazsa varsayılan zincir
+pub fn chain_for(store: &ChatStore, url: &str) -> (String, Vec<StepConfig>) {
+    let host = url::Url::parse(url).ok().and_then(|u| u.host_str().map(|h| h.to_lowercase())).unwrap_or_default();
+    load_chains(store)
+        .into_iter()
+        .filter_map(|c| pattern_score(&c.pattern, &host).map(|score| (score, c)))
+        .max_by_key(|(score, _)| *score)
+        .map(|(_, c)| (c.pattern, c.steps))
+        .unwrap_or_else(|| ("varsayılan".to_string(), default_steps()))
+}
+
+// Zincirde canlı DOM adımı açık mı (açıksa sayfa her soruda açık sekmeden tazelenir)
+pub fn uses_live_dom(steps: &[StepConfig]) -> bool {
+    steps.iter().any(|s| s.enabled && s.name == STEP_LIVE_DOM)
+}
+
+// Eski "content_strategy = live_dom" ayarı: tüm zincirlerde (yoksa "*" zincirinde) live_dom adımı
+// en başta açılır, ardından eski ayar silinir
+pub fn migrate_legacy_setting(store: &ChatStore) -> Result<(), String> {
+    let Some(legacy) = store.get_setting(KEY_LEGACY_CONTENT_STRATEGY)? else { return Ok(()) };
+    if legacy.trim() == STEP_LIVE_DOM {
+        let mut chains = load_chains(store);
+        if !chains.iter().any(|c| c.pattern.trim() == "*") {
+            chains.push(DomainChain { pattern: "*".to_string(), steps: default_steps() });
+        }
+        for chain in &mut chains {
+            chain.steps.retain(|s| s.name != STEP_LIVE_DOM);
+            chain.steps.insert(0, StepConfig { name: STEP_LIVE_DOM.to_string(), enabled: true, timeout_secs: DEFAULT_STEPS[0].1 });
+        }
+        save_chains(store, &chains)?;
+    }
+    store.delete_setting(KEY_LEGACY_CONTENT_STRATEGY)
+}
+
+// Eşleşme özgüllüğü: tam eşleşme > joker alt alan adı (uzun olan önce) > "*"
+pub fn pattern_score(pattern: &str, host: &str) -> Option<usize> {
+    let pattern = pattern.trim().to_lowercase();
+    let pattern = pattern.trim_start_matches("www.");
+    let host = host.trim_start_matches("www.");
+    if pattern == "*" { return Some(0); }
+    if let Some(suffix) = pattern.strip_prefix("*.") {
+        let matches = host == suffix || host.ends_with(&format!(".{}", suffix));
+        return matches.then_some(suffix.len() * 2);
+    }
+    (host == pattern).then_some(pattern.len() * 2 + 1)
+}
+
+// Yarış modu: zincirdeki açık adımlar sırayla değil aynı anda çalışır; süre dolmadan gelen
+// en yüksek puanlı sonuç seçilir, hâlâ çalışan adımlar iptal edilir. firecrawl_crawl yarışa girmez;
+// hiçbir adım sayfa getiremezse son çare olarak çalışır.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RaceOptions {
+    pub enabled: bool,
+    pub deadline_secs: u64,
+}
+
+impl Default for RaceOptions {
+    fn default() -> Self {
+        Self { enabled: false, deadline_secs: 20 }
+    }
+}
+
+impl RaceOptions {
+    pub fn load(store: &ChatStore) -> Self {
+        store
+            .get_setting(KEY_RACE)
+            .unwrap_or(None)
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, store: &ChatStore) -> Result<(), String> {
+        if self.deadline_secs == 0 || self.deadline_secs > MAX_TIMEOUT_SECS {
+            return Err(format!("Yarış süresi 1-{} sn olmalı", MAX_TIMEOUT_SECS));
+        }
+        let raw = serde_json::to_string(self).map_err(|e| format!("Yarış ayarları serileştirilemedi: {}", e))?;
+        store.set_setting(KEY_RACE, &raw)
+    }
+
+    pub fn deadline(&self) -> Duration {
+        Duration::from_secs(self.deadline_secs)
+    }
+}
+
+// Bu puanı geçen sonuç gelince diğerleri beklenmez
+pub const DECISIVE_SCORE: f64 = 2.0;
+// İlk kullanılabilir sonuçtan sonra daha iyisi için en fazla bu kadar beklenir
+pub const RACE_GRACE: Duration = Duration::from_millis(1500);
+// Bunun altındaki sonuçlar yalnızca başka hiçbir şey yoksa kullanılır
+pub const USABLE_SCORE: f64 = 0.5;
+
+// Sonuç puanı: adımın güvenilirliği x içerik uzunluğu (logaritmik), yapı (başlık/tablo) için küçük bonus.
+// Siteye özel çıkarıcılar ve Firecrawl genelde daha temiz içerik verdiği için ağırlıkları yüksek.
+pub fn score(step: &str, content: &str) -> f64 {
+    let weight = match step {
+        STEP_EXTRACTORS => 3.0,
+        STEP_FIRECRAWL_SCRAPE | STEP_FIRECRAWL_CRAWL => 2.5,
+        STEP_LIVE_DOM | STEP_AGGRESSIVE_HTML => 1.5,
+        _ => 1.0,
+    };
+    let len = content.trim().len() as f64;
+    if len < 1.0 { return 0.0; }
+    let mut quality = (len.ln() / 10_000f64.ln()).min(1.2);
+    if len < 200.0 { quality *= 0.2; }
+    let structured = content.contains("\n#") || content.contains("\n|");
+    weight * quality + if structured { 0.1 } else { 0.0 }
+}
+
+// Deneme raporu satırı
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attempt {
+    pub strategy: String,
+    pub duration_ms: u64,
+    pub outcome: &'static str, // success | failed | timeout | skipped | disabled | discarded | cancelled
+    pub error: Option<String>,
+    // Yarış modunda sonucun puanı
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+impl Attempt {
+    pub fn disabled(strategy: &str) -> Self {
+        Self { strategy: strategy.to_string(), duration_ms: 0, outcome: "disabled", error: None, score: None }
+    }
+
+    pub fn skipped(strategy: &str, reason: &str) -> Self {
+        Self { strategy: strategy.to_string(), duration_ms: 0, outcome: "skipped", error: Some(reason.to_string()), score: None }
+    }
+
+    pub fn finished(strategy: &str, started: Instant, result: Result<(), String>) -> Self {
+        let duration_ms = started.elapsed().as_millis() as u64;
+        match result {
+            Ok(()) => Self { strategy: strategy.to_string(), duration_ms, outcome: "success", error: None, score: None },
+            Err(e) => Self { strategy: strategy.to_string(), duration_ms, outcome: "failed", error: Some(e), score: None },
+        }
+    }
+
+    pub fn timed_out(strategy: &str, started: Instant, limit: Duration) -> Self {
+        Self {
+            strategy: strategy.to_string(),
+            duration_ms: started.elapsed().as_millis() as u64,
+            outcome: "timeout",
+            error: Some(format!("{} sn içinde tamamlanmadı", limit.as_secs())),
+            score: None,
+        }
+    }
+
+    // Yarışı kazanan adım
+    pub fn won(strategy: &str, duration: Duration, score: f64) -> Self {
+        Self { strategy: strategy.to_string(), duration_ms: duration.as_millis() as u64, outcome: "success", error: None, score: Some(score) }
+    }
+
+    // Yarışta daha yüksek puanlı sonuç seçildiği için kullanılmayan başarılı adım
+    pub fn discarded(strategy: &str, duration: Duration, score: f64) -> Self {
+        Self { strategy: strategy.to_string(), duration_ms: duration.as_millis() as u64, outcome: "discarded", error: None, score: Some(score) }
+    }
+
+    // Yarış bittiğinde hâlâ çalışan (iptal edilen) adım
+    pub fn cancelled(strategy: &str, started: Instant) -> Self {
+        Self { strategy: strategy.to_string(), duration_ms: started.elapsed().as_millis() as u64, outcome: "cancelled", error: None, score: None }
+    }
+}
+
+// Tüm adımlar başarısız olduğunda kullanıcıya gösterilecek özet
+pub fn failure_summary(attempts: &[Attempt]) -> String {
+    let ran: Vec<String> = attempts
+        .iter()
+        .filter(|a| a.outcome != "disabled")
+        .map(|a| format!("{}: {}", a.strategy, a.error.as_deref().unwrap_or(a.outcome)))
+        .collect();
+    if ran.is_empty() {
+        return "İçerik alınamadı: bu alan adı için tüm adımlar kapalı".to_string();
+    }
+    format!("İçerik alınamadı ({})", ran.join("; "))
+}
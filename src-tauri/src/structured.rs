@@ -0,0 +1,609 @@
+// schema.org yapılandırılmış veri çıkarımı.
+// Birincil kaynak JSON-LD: diziler, @graph ve iç içe nesneler gezilir, {"@id"} referansları
+// aynı belgedeki düğümlere çözülür. JSON-LD'de bulunmayan türler için microdata (itemscope)
+// ve RDFa (typeof/property) ikincil kaynak olarak okunur. Sonuç modele verilecek kısa bir
+// "Yapılandırılmış veri" bölümüne çevrilir.
+
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::readability::{collapse_ws, truncate_utf8};
+
+// Bölümün bağlamda kaplayacağı en fazla bayt
+const MAX_SECTION_BYTES: usize = 6000;
+// Uzun metin alanları (articleBody, açıklama, cevap) için kesme sınırı. Sayfanın okunabilir gövdesi
+// yoksa makale gövdesi kesilmez (bkz. render_section).
+const MAX_FIELD_BYTES: usize = 600;
+const MAX_LIST_ITEMS: usize = 25;
+
+const ARTICLE_TYPES: &[&str] = &["Article", "NewsArticle", "BlogPosting", "ReportageNewsArticle", "TechArticle", "ScholarlyArticle"];
+const ORGANIZATION_TYPES: &[&str] = &["Organization", "Corporation", "NewsMediaOrganization", "LocalBusiness", "Store", "Restaurant", "EducationalOrganization"];
+const RENDERED_TYPES: &[&str] = &["Product", "Recipe", "Event", "FAQPage", "HowTo", "BreadcrumbList"];
+
+// Belgedeki tüm yapılandırılmış veriyi okuyup markdown bölümü döndürür (yoksa boş dize).
+// `full_article`: sayfanın okunabilir gövdesi boş/çok kısa; gövdesi yalnızca JSON-LD'de olan sayfalarda
+// makale gövdesi (articleBody / text) kesilmeden verilir ve bölüm sınırına sayılmaz. Aksi halde aynı
+// metnin ikinci kopyası sayfa bütçesini tüketmesin diye diğer alanlar gibi kısaltılır.
+pub fn render_section(doc: &Html, full_article: bool) -> String {
+    let mut items = json_ld_items(doc);
+    let ld_types: HashSet<String> = items.iter().filter_map(primary_type).collect();
+
+    // İkincil kaynaklar: JSON-LD'de zaten olan türler tekrar eklenmez
+    for item in microdata_items(doc).into_iter().chain(rdfa_items(doc)) {
+        if let Some(t) = primary_type(&item) {
+            if !ld_types.contains(&t) { items.push(item); }
+        }
+    }
+
+    let mut blocks: Vec<(String, bool)> = Vec::new();
+    let mut seen = HashSet::new();
+    for item in &items {
+        let Some(block) = render_item(item, full_article) else { continue };
+        // Aynı nesne birden fazla kaynaktan gelebilir
+        if seen.insert(block.clone()) { blocks.push((block, full_article && is_article(item))); }
+    }
+    if blocks.is_empty() { return String::new(); }
+
+    // Tam gövdeli makale blokları bölüm sınırına sayılmaz (gövde sayfa bütçesiyle kesilir)
+    let mut out = String::from("Yapılandırılmış veri (schema.org):\n");
+    let mut used = 0;
+    let mut skipped = false;
+    for (block, article) in blocks {
+        if !article {
+            if used + block.len() > MAX_SECTION_BYTES {
+                skipped = true;
+                continue;
+            }
+            used += block.len();
+        }
+        out.push_str(&block);
+        out.push('\n');
+    }
+    if skipped { out.push_str("[... kalan yapılandırılmış veri atlandı]\n"); }
+    out
+}
+
+// Sayfa meta verisi (metadata.rs) için belgedeki ilk makale nesnesi; JSON-LD yoksa microdata/RDFa
+pub fn article_item(doc: &Html) -> Option<Value> {
+    json_ld_items(doc)
+        .into_iter()
+        .chain(microdata_items(doc))
+        .chain(rdfa_items(doc))
+        .find(|v| types_of(v).iter().any(|t| ARTICLE_TYPES.contains(&t.as_str())))
+}
+
+// ---- JSON-LD ----
+
+fn json_ld_items(doc: &Html) -> Vec<Value> {
+    let sel = match Selector::parse("script") { Ok(s) => s, Err(_) => return Vec::new() };
+    let mut roots = Vec::new();
+    for script in doc.select(&sel) {
+        let is_ld = script.value().attr("type").map(|t| t.trim().eq_ignore_ascii_case("application/ld+json")).unwrap_or(false);
+        if !is_ld { continue; }
+        let raw: String = script.text().collect();
+        if let Some(v) = parse_json_ld(&raw) { roots.push(v); }
+    }
+
+    // Önce tüm düğümleri @id'ye göre indeksle, sonra türlü düğümleri topla
+    let mut by_id: HashMap<String, Value> = HashMap::new();
+    for root in &roots { index_ids(root, &mut by_id); }
+    let mut items = Vec::new();
+    for root in &roots { collect_typed(root, &by_id, &mut items, 0); }
+    items
+}
+
+// Bazı siteler JSON'u HTML yorumu / CDATA içine sarar ya da sona fazladan virgül koyar
+fn parse_json_ld(raw: &str) -> Option<Value> {
+    let cleaned = raw
+        .trim()
+        .trim_start_matches("<!--").trim_end_matches("-->")
+        .trim()
+        .trim_start_matches("//<![CDATA[").trim_end_matches("//]]>")
+        .trim();
+    if let Ok(v) = serde_json::from_str::<Value>(cleaned) { return Some(v); }
+    // Arkada çöp varsa ilk değeri al
+    serde_json::Deserializer::from_str(cleaned).into_iter::<Value>().next()?.ok()
+}
+
+fn index_ids(v: &Value, by_id: &mut HashMap<String, Value>) {
+    match v {
+        Value::Array(arr) => arr.iter().for_each(|x| index_ids(x, by_id)),
+        Value::Object(map) => {
+            if let Some(id) = map.get("@id").and_then(|i| i.as_str()) {
+                // Yalnızca {"@id": ..} olan referanslar yerine içi dolu tanımı sakla
+                if map.len() > 1 { by_id.entry(id.to_string()).or_insert_with(|| v.clone()); }
+            }
+            map.values().for_each(|x| index_ids(x, by_id));
+        }
+        _ => {}
+    }
+}
+
+// Desteklenen türdeki nesneleri toplar. Desteklenen bir nesnenin alt alanları o nesnenin
+// renderer'ı tarafından işlendiği için içine tekrar inilmez; WebPage gibi diğer türlerin
+// içindeki (örn. mainEntity: Product) nesneler ise ayrıca toplanır.
+fn collect_typed(v: &Value, by_id: &HashMap<String, Value>, out: &mut Vec<Value>, depth: usize) {
+    if depth > 12 { return; }
+    match v {
+        Value::Array(arr) => arr.iter().for_each(|x| collect_typed(x, by_id, out, depth + 1)),
+        Value::Object(map) => {
+            if let Some(graph) = map.get("@graph") {
+                collect_typed(graph, by_id, out, depth + 1);
+            }
+            if is_supported(v) {
+                out.push(resolve_refs(v, by_id, 0));
+                return;
+            }
+            for (k, x) in map {
+                if k != "@graph" { collect_typed(x, by_id, out, depth + 1); }
+            }
+        }
+        _ => {}
+    }
+}
+
+// {"@id": "..."} biçimindeki referansları tanımlarıyla değiştirir (döngülere karşı derinlik sınırlı)
+fn resolve_refs(v: &Value, by_id: &HashMap<String, Value>, depth: usize) -> Value {
+    if depth > 4 { return v.clone(); }
+    match v {
+        Value::Array(arr) => Value::Array(arr.iter().map(|x| resolve_refs(x, by_id, depth + 1)).collect()),
+        Value::Object(map) => {
+            if map.len() == 1 {
+                if let Some(target) = map.get("@id").and_then(|i| i.as_str()).and_then(|id| by_id.get(id)) {
+                    return resolve_refs(target, by_id, depth + 1);
+                }
+            }
+            let resolved: Map<String, Value> = map.iter().map(|(k, x)| (k.clone(), resolve_refs(x, by_id, depth + 1))).collect();
+            Value::Object(resolved)
+        }
+        _ => v.clone(),
+    }
+}
+
+// ---- Microdata ----
+
+fn microdata_items(doc: &Html) -> Vec<Value> {
+    let sel = match Selector::parse("[itemscope][itemtype]") { Ok(s) => s, Err(_) => return Vec::new() };
+    doc.select(&sel)
+        // Yalnızca başka bir nesnenin özelliği olmayan (en üst) nesneler
+        .filter(|el| el.value().attr("itemprop").is_none())
+        .map(|el| microdata_object(el, 0))
+        .collect()
+}
+
+fn microdata_object(scope: ElementRef<'_>, depth: usize) -> Value {
+    let mut map = Map::new();
+    if let Some(t) = scope.value().attr("itemtype") {
+        map.insert("@type".to_string(), Value::String(t.split_whitespace().next().unwrap_or("").to_string()));
+    }
+    if depth < 6 {
+        collect_props(scope, depth, "itemprop", "itemscope", &mut map, &|el, d| microdata_object(el, d));
+    }
+    Value::Object(map)
+}
+
+// ---- RDFa (Lite) ----
+
+fn rdfa_items(doc: &Html) -> Vec<Value> {
+    let sel = match Selector::parse("[typeof]") { Ok(s) => s, Err(_) => return Vec::new() };
+    doc.select(&sel)
+        .filter(|el| el.value().attr("property").is_none())
+        .map(|el| rdfa_object(el, 0))
+        .collect()
+}
+
+fn rdfa_object(scope: ElementRef<'_>, depth: usize) -> Value {
+    let mut map = Map::new();
+    if let Some(t) = scope.value().attr("typeof") {
+        map.insert("@type".to_string(), Value::String(t.split_whitespace().next().unwrap_or("").to_string()));
+    }
+    if depth < 6 {
+        collect_props(scope, depth, "property", "typeof", &mut map, &|el, d| rdfa_object(el, d));
+    }
+    Value::Object(map)
+}
+
+// Kapsam içindeki özellikleri toplar; iç içe bir kapsama (itemscope / typeof) rastlanınca
+// onun altına inilmez, o öğe alt nesne olarak eklenir.
+fn collect_props(
+    scope: ElementRef<'_>,
+    depth: usize,
+    prop_attr: &str,
+    scope_attr: &str,
+    map: &mut Map<String, Value>,
+    nested: &dyn Fn(ElementRef<'_>, usize) -> Value,
+) {
+    for child in scope.children().filter_map(ElementRef::wrap) {
+        let is_scope = child.value().attr(scope_attr).is_some();
+        if let Some(props) = child.value().attr(prop_attr) {
+            let value = if is_scope { nested(child, depth + 1) } else { Value::String(property_value(child)) };
+            for prop in props.split_whitespace() {
+                // RDFa'da "schema:name" gibi önekler olabilir
+                let key = prop.rsplit([':', '/']).next().unwrap_or(prop).to_string();
+                match map.get_mut(&key) {
+                    Some(Value::Array(arr)) => arr.push(value.clone()),
+                    Some(existing) => { let prev = existing.take(); *existing = Value::Array(vec![prev, value.clone()]); }
+                    None => { map.insert(key, value.clone()); }
+                }
+            }
+        }
+        if !is_scope { collect_props(child, depth, prop_attr, scope_attr, map, nested); }
+    }
+}
+
+fn property_value(el: ElementRef<'_>) -> String {
+    let v = el.value();
+    if let Some(c) = v.attr("content") { return c.trim().to_string(); }
+    let attr = match v.name() {
+        "a" | "link" | "area" => v.attr("href"),
+        "img" | "audio" | "video" | "source" | "iframe" | "embed" => v.attr("src"),
+        "time" => v.attr("datetime"),
+        "data" | "meter" => v.attr("value"),
+        "object" => v.attr("data"),
+        _ => None,
+    };
+    attr.map(|s| s.trim().to_string()).unwrap_or_else(|| collapse_ws(&el.text().collect::<String>()))
+}
+
+// ---- Tür yardımcıları ----
+
+// "http://schema.org/Product", "schema:Product" -> "Product"
+fn short_type(t: &str) -> String {
+    t.trim().rsplit(['/', ':', '#']).next().unwrap_or(t).to_string()
+}
+
+fn types_of(v: &Value) -> Vec<String> {
+    match v.get("@type") {
+        Some(Value::String(s)) => vec![short_type(s)],
+        Some(Value::Array(arr)) => arr.iter().filter_map(|x| x.as_str()).map(short_type).collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn primary_type(v: &Value) -> Option<String> {
+    let types = types_of(v);
+    types.iter().find(|t| is_known(t)).cloned().or_else(|| types.first().cloned())
+}
+
+fn is_known(t: &str) -> bool {
+    RENDERED_TYPES.contains(&t) || ARTICLE_TYPES.contains(&t) || ORGANIZATION_TYPES.contains(&t)
+}
+
+fn is_supported(v: &Value) -> bool {
+    types_of(v).iter().any(|t| is_known(t))
+}
+
+fn has_type(v: &Value, t: &str) -> bool {
+    types_of(v).iter().any(|x| x == t)
+}
+
+// Tek bir değeri okunabilir metne çevirir: dize, sayı, {name}/{@value}/{text}, dizinin ilk öğesi
+pub(crate) fn text(v: Option<&Value>) -> Option<String> {
+    let s = match v? {
+        Value::String(s) => clean_text(s),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Array(arr) => return arr.iter().find_map(|x| text(Some(x))),
+        Value::Object(map) => {
+            return ["name", "@value", "text", "value"].iter().find_map(|k| text(map.get(*k)));
+        }
+        Value::Null => return None,
+    };
+    if s.is_empty() { None } else { Some(s) }
+}
+
+// Dizi ya da tek değer -> metin listesi
+pub(crate) fn texts(v: Option<&Value>) -> Vec<String> {
+    match v {
+        Some(Value::Array(arr)) => arr.iter().filter_map(|x| text(Some(x))).collect(),
+        Some(x) => text(Some(x)).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+fn as_list(v: Option<&Value>) -> Vec<&Value> {
+    match v {
+        Some(Value::Array(arr)) => arr.iter().collect(),
+        Some(x) => vec![x],
+        None => Vec::new(),
+    }
+}
+
+// Makale gövdesi: kesilmeden, etiketsiz metin
+fn full_text(v: Option<&Value>) -> Option<String> {
+    match v? {
+        Value::String(s) => Some(plain_text(s)).filter(|t| !t.is_empty()),
+        Value::Array(arr) => arr.iter().find_map(|x| full_text(Some(x))),
+        _ => None,
+    }
+}
+
+// Alan değerleri HTML içerebilir (özellikle FAQ cevapları); etiketleri atıp boşlukları toparla
+fn plain_text(s: &str) -> String {
+    let plain = if s.contains('<') {
+        Html::parse_fragment(s).root_element().text().collect::<String>()
+    } else {
+        s.to_string()
+    };
+    collapse_ws(&plain)
+}
+
+fn clean_text(s: &str) -> String {
+    let plain = plain_text(s);
+    let cut = truncate_utf8(&plain, MAX_FIELD_BYTES);
+    if cut.len() < plain.len() { format!("{}…", cut) } else { plain }
+}
+
+// "https://schema.org/InStock" -> "InStock"
+fn enum_value(v: Option<&Value>) -> Option<String> {
+    text(v).map(|s| short_type(&s))
+}
+
+// ISO 8601 süresi: PT1H30M -> "1 sa 30 dk"; anlaşılamazsa olduğu gibi
+fn duration(v: Option<&Value>) -> Option<String> {
+    let raw = text(v)?;
+    let upper = raw.to_uppercase();
+    let Some(body) = upper.strip_prefix('P') else { return Some(raw) };
+    let mut parts = Vec::new();
+    let mut num = String::new();
+    let mut in_time = false;
+    for c in body.chars() {
+        match c {
+            'T' => in_time = true,
+            '0'..='9' | '.' => num.push(c),
+            unit => {
+                let label = match (unit, in_time) {
+                    ('D', false) => "gün",
+                    ('W', false) => "hafta",
+                    ('H', true) => "sa",
+                    ('M', true) => "dk",
+                    ('S', true) => "sn",
+                    _ => return Some(raw),
+                };
+                if !num.is_empty() && num != "0" { parts.push(format!("{} {}", num, label)); }
+                num.clear();
+            }
+        }
+    }
+    if parts.is_empty() { Some(raw) } else { Some(parts.join(" ")) }
+}
+
+fn address(v: Option<&Value>) -> Option<String> {
+    match v? {
+        Value::Object(map) => {
+            let parts: Vec<String> = ["streetAddress", "addressLocality", "addressRegion", "postalCode", "addressCountry"]
+                .iter()
+                .filter_map(|k| text(map.get(*k)))
+                .collect();
+            if parts.is_empty() { text(v) } else { Some(parts.join(", ")) }
+        }
+        other => text(Some(other)),
+    }
+}
+
+fn rating(v: Option<&Value>) -> Option<String> {
+    let r = v?;
+    let value = text(r.get("ratingValue"))?;
+    let best = text(r.get("bestRating")).unwrap_or_else(|| "5".to_string());
+    let count = text(r.get("reviewCount")).or_else(|| text(r.get("ratingCount")));
+    Some(match count {
+        Some(c) => format!("{}/{} ({} değerlendirme)", value, best, c),
+        None => format!("{}/{}", value, best),
+    })
+}
+
+// Offer / AggregateOffer / dizi -> "1.299,00 TRY (InStock)" benzeri satır
+fn offer(v: Option<&Value>) -> Option<String> {
+    let offers = as_list(v);
+    let o = offers.first()?;
+    let currency = text(o.get("priceCurrency")).unwrap_or_default();
+    let price = match (text(o.get("lowPrice")), text(o.get("highPrice"))) {
+        (Some(lo), Some(hi)) if lo != hi => Some(format!("{} - {}", lo, hi)),
+        (Some(lo), _) => Some(lo),
+        _ => text(o.get("price")).or_else(|| text(o.get("priceSpecification").and_then(|p| p.get("price")))),
+    };
+    let mut s = match price {
+        Some(p) => format!("{} {}", p, currency).trim().to_string(),
+        None => String::new(),
+    };
+    if let Some(av) = enum_value(o.get("availability")) {
+        s = if s.is_empty() { av } else { format!("{} ({})", s, av) };
+    }
+    if offers.len() > 1 { s.push_str(&format!(" [+{} teklif]", offers.len() - 1)); }
+    if s.is_empty() { None } else { Some(s) }
+}
+
+// HowTo / Recipe adımları: dize, HowToStep, HowToSection (itemListElement) ya da dizi
+fn steps(v: Option<&Value>, out: &mut Vec<String>) {
+    for step in as_list(v) {
+        if out.len() >= MAX_LIST_ITEMS { return; }
+        match step {
+            Value::String(s) => {
+                let s = clean_text(s);
+                if !s.is_empty() { out.push(s); }
+            }
+            Value::Object(map) => {
+                if let Some(inner) = map.get("itemListElement") {
+                    if let Some(name) = text(map.get("name")) { out.push(format!("**{}**", name)); }
+                    steps(Some(inner), out);
+                } else if let Some(t) = text(map.get("text")).or_else(|| text(map.get("name"))) {
+                    out.push(t);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// ---- Renderer'lar ----
+
+struct Block { out: String }
+
+impl Block {
+    fn new(kind: &str, name: Option<String>) -> Self {
+        let out = match name {
+            Some(n) => format!("[{}] {}\n", kind, n),
+            None => format!("[{}]\n", kind),
+        };
+        Self { out }
+    }
+
+    fn field(&mut self, label: &str, value: Option<String>) {
+        if let Some(v) = value { self.out.push_str(&format!("- {}: {}\n", label, v)); }
+    }
+
+    fn list(&mut self, label: &str, items: &[String], numbered: bool) {
+        if items.is_empty() { return; }
+        self.out.push_str(&format!("- {}:\n", label));
+        for (i, item) in items.iter().take(MAX_LIST_ITEMS).enumerate() {
+            if numbered { self.out.push_str(&format!("  {}. {}\n", i + 1, item)); } else { self.out.push_str(&format!("  - {}\n", item)); }
+        }
+        if items.len() > MAX_LIST_ITEMS { self.out.push_str(&format!("  (+{} daha)\n", items.len() - MAX_LIST_ITEMS)); }
+    }
+
+    // Başlık dışında hiç alan yoksa bloğu atla
+    fn finish(self) -> Option<String> {
+        if self.out.lines().count() > 1 { Some(self.out) } else { None }
+    }
+}
+
+fn is_article(v: &Value) -> bool {
+    types_of(v).iter().any(|t| ARTICLE_TYPES.contains(&t.as_str()))
+}
+
+fn render_item(v: &Value, full_article: bool) -> Option<String> {
+    let name = text(v.get("name")).or_else(|| text(v.get("headline")));
+    if has_type(v, "Product") { return render_product(v, name); }
+    if has_type(v, "Recipe") { return render_recipe(v, name); }
+    if has_type(v, "Event") { return render_event(v, name); }
+    if has_type(v, "FAQPage") { return render_faq(v); }
+    if has_type(v, "HowTo") { return render_howto(v, name); }
+    if has_type(v, "BreadcrumbList") { return render_breadcrumbs(v); }
+    let types = types_of(v);
+    if is_article(v) { return render_article(v, name, full_article); }
+    if types.iter().any(|t| ORGANIZATION_TYPES.contains(&t.as_str())) { return render_organization(v, name, &types); }
+    None
+}
+
+fn render_product(v: &Value, name: Option<String>) -> Option<String> {
+    let mut b = Block::new("Ürün", name);
+    b.field("Marka", text(v.get("brand")));
+    b.field("SKU", text(v.get("sku")).or_else(|| text(v.get("gtin13"))).or_else(|| text(v.get("mpn"))));
+    b.field("Fiyat", offer(v.get("offers")));
+    b.field("Puan", rating(v.get("aggregateRating")));
+    b.field("Durum", enum_value(v.get("itemCondition")));
+    b.field("Açıklama", text(v.get("description")));
+    b.finish()
+}
+
+fn render_recipe(v: &Value, name: Option<String>) -> Option<String> {
+    let mut b = Block::new("Tarif", name);
+    b.field("Yazar", text(v.get("author")));
+    b.field("Hazırlık", duration(v.get("prepTime")));
+    b.field("Pişirme", duration(v.get("cookTime")));
+    b.field("Toplam süre", duration(v.get("totalTime")));
+    b.field("Porsiyon", text(v.get("recipeYield")));
+    b.field("Kalori", text(v.get("nutrition").and_then(|n| n.get("calories"))));
+    b.field("Puan", rating(v.get("aggregateRating")));
+    b.list("Malzemeler", &texts(v.get("recipeIngredient").or_else(|| v.get("ingredients"))), false);
+    let mut instructions = Vec::new();
+    steps(v.get("recipeInstructions"), &mut instructions);
+    b.list("Yapılışı", &instructions, true);
+    b.finish()
+}
+
+fn render_event(v: &Value, name: Option<String>) -> Option<String> {
+    let mut b = Block::new("Etkinlik", name);
+    b.field("Başlangıç", text(v.get("startDate")));
+    b.field("Bitiş", text(v.get("endDate")));
+    let location = as_list(v.get("location")).into_iter().find_map(|loc| {
+        if has_type(loc, "VirtualLocation") { return text(loc.get("url")).map(|u| format!("Çevrim içi ({})", u)); }
+        let name = text(loc.get("name"));
+        let addr = address(loc.get("address"));
+        match (name, addr) {
+            (Some(n), Some(a)) if n != a => Some(format!("{}, {}", n, a)),
+            (Some(n), _) => Some(n),
+            (None, a) => a.or_else(|| text(Some(loc))),
+        }
+    });
+    b.field("Yer", location);
+    b.field("Durum", enum_value(v.get("eventStatus")));
+    b.field("Katılım", enum_value(v.get("eventAttendanceMode")));
+    b.field("Düzenleyen", text(v.get("organizer")));
+    b.field("Sanatçı", text(v.get("performer")));
+    b.field("Bilet", offer(v.get("offers")));
+    b.field("Açıklama", text(v.get("description")));
+    b.finish()
+}
+
+fn render_faq(v: &Value) -> Option<String> {
+    let mut b = Block::new("SSS", text(v.get("name")));
+    for q in as_list(v.get("mainEntity")).into_iter().take(MAX_LIST_ITEMS) {
+        let Some(question) = text(q.get("name")) else { continue };
+        let answer = text(q.get("acceptedAnswer")
+            .map(|a| a.get("text").unwrap_or(a))
+            .or_else(|| q.get("suggestedAnswer")));
+        b.out.push_str(&format!("- S: {}\n", question));
+        if let Some(a) = answer { b.out.push_str(&format!("  C: {}\n", a)); }
+    }
+    b.finish()
+}
+
+fn render_howto(v: &Value, name: Option<String>) -> Option<String> {
+    let mut b = Block::new("Nasıl yapılır", name);
+    b.field("Toplam süre", duration(v.get("totalTime")));
+    b.field("Maliyet", text(v.get("estimatedCost")));
+    b.list("Malzemeler", &texts(v.get("supply")), false);
+    b.list("Araçlar", &texts(v.get("tool")), false);
+    let mut list = Vec::new();
+    steps(v.get("step"), &mut list);
+    b.list("Adımlar", &list, true);
+    b.finish()
+}
+
+fn render_breadcrumbs(v: &Value) -> Option<String> {
+    let mut crumbs: Vec<(i64, String)> = as_list(v.get("itemListElement"))
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, el)| {
+            let pos = el.get("position").and_then(|p| p.as_i64().or_else(|| p.as_str().and_then(|s| s.parse().ok()))).unwrap_or(i as i64);
+            let name = text(el.get("name")).or_else(|| text(el.get("item")))?;
+            Some((pos, name))
+        })
+        .collect();
+    if crumbs.is_empty() { return None; }
+    crumbs.sort_by_key(|(p, _)| *p);
+    let path: Vec<String> = crumbs.into_iter().map(|(_, n)| n).collect();
+    Some(format!("[Konum] {}\n", path.join(" > ")))
+}
+
+fn render_article(v: &Value, name: Option<String>, full_body: bool) -> Option<String> {
+    let mut b = Block::new("Makale", name);
+    let authors = texts(v.get("author"));
+    b.field("Yazar", if authors.is_empty() { None } else { Some(authors.join(", ")) });
+    b.field("Yayın", text(v.get("datePublished")));
+    b.field("Güncelleme", text(v.get("dateModified")));
+    b.field("Yayıncı", text(v.get("publisher")));
+    b.field("Açıklama", text(v.get("description")));
+    let body = |key| if full_body { full_text(v.get(key)) } else { text(v.get(key)) };
+    b.field("İçerik", body("articleBody").or_else(|| body("text")));
+    b.finish()
+}
+
+fn render_organization(v: &Value, name: Option<String>, types: &[String]) -> Option<String> {
+    let kind = if types.iter().any(|t| t == "LocalBusiness" || t == "Store" || t == "Restaurant") { "İşletme" } else { "Kuruluş" };
+    let mut b = Block::new(kind, name);
+    b.field("Web", text(v.get("url")));
+    b.field("Adres", address(v.get("address")));
+    b.field("Telefon", text(v.get("telephone")));
+    b.field("E-posta", text(v.get("email")));
+    b.field("Çalışma saatleri", {
+        let hours = texts(v.get("openingHours"));
+        if hours.is_empty() { None } else { Some(hours.join("; ")) }
+    });
+    b.field("Puan", rating(v.get("aggregateRating")));
+    let same_as = texts(v.get("sameAs"));
+    if !same_as.is_empty() { b.field("Profiller", Some(same_as.into_iter().take(5).collect::<Vec<_>>().join(", "))); }
+    b.finish()
+}
@@ -0,0 +1,277 @@
+// HTML tablolarını satır/sütun ızgarasına çevirir.
+// colspan/rowspan hücreleri kapladıkları her konuma kopyalanır (pandas read_html gibi),
+// <thead> ya da yalnızca <th> içeren baştaki satırlar başlık kabul edilir; birden çok başlık
+// satırı "Üst / Alt" biçiminde birleştirilir. Model bağlamı için GFM, dışa aktarma için CSV/JSON üretir.
+
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+
+use crate::markdown::{self, Ctx};
+use crate::readability;
+
+// Bozuk colspan/rowspan değerlerinin ızgarayı şişirmemesi için üst sınır
+const MAX_SPAN: usize = 50;
+// Ana içerik dışındaki tablolar bölümü için sınırlar
+const MAX_SECTION_TABLES: usize = 8;
+const MAX_SECTION_BYTES: usize = 6000;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Table {
+    pub caption: Option<String>,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+struct RawCell {
+    text: String,
+    colspan: usize,
+    rowspan: usize,
+    header: bool,
+}
+
+struct RawRow {
+    cells: Vec<RawCell>,
+    in_head: bool,
+    in_foot: bool,
+}
+
+// Sayfadaki veri tablolarını düz metin hücrelerle döndürür (dışa aktarma için)
+pub fn extract_all(html: &str) -> Vec<Table> {
+    let doc = Html::parse_document(html);
+    let Ok(sel) = Selector::parse("table") else { return Vec::new() };
+    doc.select(&sel).filter_map(|t| parse_table(t, None)).collect()
+}
+
+// Ana içerikte yer almayan (ya da readability budamasıyla düşen) veri tabloları; bilgi kutuları,
+// fiyat/özellik tabloları gibi. Ana içerikteki tablolar zaten GFM olarak yazıldığı için tekrarlanmaz.
+pub fn render_section(doc: &Html, main: Option<ElementRef<'_>>, cx: &Ctx<'_>) -> String {
+    let Ok(sel) = Selector::parse("table") else { return String::new() };
+    let mut out = String::new();
+    let mut count = 0;
+    for el in doc.select(&sel) {
+        // İç içe tablolar dıştaki tablonun hücresinde zaten yer alır
+        if el.ancestors().filter_map(ElementRef::wrap).any(|a| a.value().name() == "table") { continue; }
+        let inside_main = main.map(|m| el.ancestors().any(|a| a.id() == m.id())).unwrap_or(false);
+        if inside_main && !readability::has_unlikely_ancestor(el) { continue; }
+        let Some(table) = parse_table(el, Some(cx)) else { continue };
+        let md = table.to_markdown();
+        if out.len() + md.len() > MAX_SECTION_BYTES { break; }
+        out.push_str(&md);
+        out.push('\n');
+        count += 1;
+        if count >= MAX_SECTION_TABLES { break; }
+    }
+    if out.is_empty() { return out; }
+    format!("Tablolar:\n{}", out)
+}
+
+// Veri tablosu değilse (tek sütun/tek satır yerleşim tablosu, role=presentation) None döner.
+// cx verilirse hücreler satır içi Markdown (bağlantı, kalın vb.) olarak, verilmezse düz metin alınır.
+pub fn parse_table(el: ElementRef<'_>, cx: Option<&Ctx<'_>>) -> Option<Table> {
+    if matches!(el.value().attr("role"), Some("presentation") | Some("none")) { return None; }
+
+    let mut caption = el
+        .children()
+        .filter_map(ElementRef::wrap)
+        .find(|c| c.value().name() == "caption")
+        .map(|c| cell_text(c, cx))
+        .filter(|c| !c.is_empty());
+
+    let mut rows = collect_rows(el, cx);
+    // <tfoot> kaynakta tbody'den önce gelebilir; görüntülendiği gibi sona alınır
+    rows.sort_by_key(|r| r.in_foot);
+
+    // Tüm genişliği kaplayan tek hücreli ilk satır (bilgi kutusu adı gibi) başlık değil, tablo adıdır
+    let title_row = rows.len() > 2 && rows[0].cells.len() == 1 && rows[0].cells[0].colspan > 1;
+    if title_row {
+        let title = rows.remove(0).cells.remove(0).text;
+        if caption.is_none() && !title.is_empty() { caption = Some(title); }
+    }
+
+    let head_rows = match rows.iter().filter(|r| r.in_head).count() {
+        0 => rows.iter().take_while(|r| !r.cells.is_empty() && r.cells.iter().all(|c| c.header)).count(),
+        n => n,
+    };
+    let grid = build_grid(&rows);
+    let cols = grid.iter().map(Vec::len).max().unwrap_or(0);
+    if grid.len() < 2 || cols < 2 { return None; }
+
+    // Her satırı <th> ile başlayan anahtar/değer tablosunun sütun başlığı yoktur
+    let row_headed = head_rows == 0 && rows.iter().all(|r| r.cells.first().map(|c| c.header).unwrap_or(false));
+    let (head, body) = if head_rows > 0 && head_rows < grid.len() {
+        grid.split_at(head_rows)
+    } else if row_headed {
+        grid.split_at(0)
+    } else {
+        // Başlık yoksa ilk satır başlık olur (GFM başlık satırı ister)
+        grid.split_at(1)
+    };
+
+    let mut headers = Vec::with_capacity(cols);
+    for c in 0..cols {
+        let mut parts: Vec<&str> = Vec::new();
+        for row in head {
+            let v = row.get(c).map(String::as_str).unwrap_or("");
+            if !v.is_empty() && parts.last() != Some(&v) { parts.push(v); }
+        }
+        headers.push(parts.join(" / "));
+    }
+    let rows = body
+        .iter()
+        .map(|r| (0..cols).map(|c| r.get(c).cloned().unwrap_or_default()).collect::<Vec<_>>())
+        .filter(|r| r.iter().any(|c| !c.is_empty()))
+        .collect::<Vec<_>>();
+    if rows.is_empty() { return None; }
+
+    Some(Table { caption, headers, rows })
+}
+
+// Yalnızca bu tabloya ait satırlar (iç içe tabloların satırları hariç)
+fn collect_rows(el: ElementRef<'_>, cx: Option<&Ctx<'_>>) -> Vec<RawRow> {
+    let Ok(tr_sel) = Selector::parse("tr") else { return Vec::new() };
+    let mut rows = Vec::new();
+    for tr in el.select(&tr_sel) {
+        let owner = tr.ancestors().filter_map(ElementRef::wrap).find(|a| a.value().name() == "table");
+        if owner.map(|o| o.id()) != Some(el.id()) { continue; }
+        let section = tr.parent().and_then(ElementRef::wrap).map(|p| p.value().name().to_string()).unwrap_or_default();
+        let cells = tr
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|c| matches!(c.value().name(), "td" | "th"))
+            .map(|c| RawCell {
+                text: cell_text(c, cx),
+                colspan: span(c, "colspan").unwrap_or(1).max(1),
+                rowspan: span(c, "rowspan").unwrap_or(1),
+                header: c.value().name() == "th",
+            })
+            .collect();
+        rows.push(RawRow { cells, in_head: section == "thead", in_foot: section == "tfoot" });
+    }
+    rows
+}
+
+fn span(cell: ElementRef<'_>, attr: &str) -> Option<usize> {
+    cell.value().attr(attr).and_then(|s| s.trim().parse::<usize>().ok()).map(|n| n.min(MAX_SPAN))
+}
+
+fn cell_text(cell: ElementRef<'_>, cx: Option<&Ctx<'_>>) -> String {
+    match cx {
+        Some(cx) => markdown::inline(cell, cx),
+        None => readability::collapse_ws(&cell.text().collect::<String>()),
+    }
+}
+
+// Hücreleri yerleştirir; üstteki satırlardan sarkan rowspan hücreleri önce doldurulur.
+// rowspan="0" tablonun sonuna kadar uzanır.
+fn build_grid(rows: &[RawRow]) -> Vec<Vec<String>> {
+    let mut grid = Vec::with_capacity(rows.len());
+    // sütun -> (metin, kalan satır)
+    let mut carry: Vec<Option<(String, usize)>> = Vec::new();
+    for (r, row) in rows.iter().enumerate() {
+        let mut line: Vec<String> = Vec::new();
+        let mut cells = row.cells.iter();
+        let mut col = 0usize;
+        loop {
+            if let Some(Some((text, left))) = carry.get_mut(col) {
+                line.push(text.clone());
+                *left -= 1;
+                if *left == 0 { carry[col] = None; }
+                col += 1;
+                continue;
+            }
+            let Some(cell) = cells.next() else { break };
+            let rowspan = if cell.rowspan == 0 { rows.len() - r } else { cell.rowspan.min(rows.len() - r) };
+            for _ in 0..cell.colspan {
+                line.push(cell.text.clone());
+                if carry.len() <= col { carry.resize(col + 1, None); }
+                if rowspan > 1 { carry[col] = Some((cell.text.clone(), rowspan - 1)); }
+                col += 1;
+            }
+        }
+        // Satır bittikten sonra sağ taraftan sarkan rowspan'ler
+        while col < carry.len() {
+            match carry[col].take() {
+                Some((text, left)) => {
+                    line.push(text.clone());
+                    if left > 1 { carry[col] = Some((text, left - 1)); }
+                }
+                None => line.push(String::new()),
+            }
+            col += 1;
+        }
+        while line.last().map(String::is_empty).unwrap_or(false) { line.pop(); }
+        grid.push(line);
+    }
+    grid
+}
+
+impl Table {
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        if let Some(caption) = &self.caption {
+            out.push_str(&format!("**{}**\n\n", caption));
+        }
+        push_md_row(&mut out, &self.headers);
+        out.push('|');
+        for _ in &self.headers { out.push_str(" --- |"); }
+        out.push('\n');
+        for row in &self.rows { push_md_row(&mut out, row); }
+        out
+    }
+
+    // RFC 4180: virgül, tırnak ya da satır sonu içeren alanlar tırnaklanır.
+    // Sütun başlığı olmayan (anahtar/değer) tablolarda başlık satırı yazılmaz.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::new();
+        let header = Some(&self.headers).filter(|h| h.iter().any(|c| !c.is_empty()));
+        for row in header.into_iter().chain(self.rows.iter()) {
+            let line: Vec<String> = row.iter().map(|v| csv_field(v)).collect();
+            out.push_str(&line.join(","));
+            out.push_str("\r\n");
+        }
+        out
+    }
+
+    // Her satır başlık -> değer nesnesi; boş ya da tekrarlanan başlıklar ayırt edilir
+    pub fn to_records(&self) -> serde_json::Value {
+        let mut keys: Vec<String> = Vec::with_capacity(self.headers.len());
+        for (i, h) in self.headers.iter().enumerate() {
+            let base = if h.is_empty() { format!("Sütun {}", i + 1) } else { h.clone() };
+            let mut key = base.clone();
+            let mut n = 2;
+            while keys.contains(&key) {
+                key = format!("{} ({})", base, n);
+                n += 1;
+            }
+            keys.push(key);
+        }
+        let records = self
+            .rows
+            .iter()
+            .map(|row| {
+                let obj = keys.iter().cloned().zip(row.iter().map(|v| serde_json::Value::String(v.clone()))).collect();
+                serde_json::Value::Object(obj)
+            })
+            .collect();
+        serde_json::Value::Array(records)
+    }
+}
+
+fn push_md_row(out: &mut String, cells: &[String]) {
+    out.push('|');
+    for c in cells {
+        out.push(' ');
+        out.push_str(&c.replace('|', "\\|"));
+        out.push_str(" |");
+    }
+    out.push('\n');
+}
+
+fn csv_field(v: &str) -> String {
+    if v.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", v.replace('"', "\"\""))
+    } else {
+        v.to_string()
+    }
+}